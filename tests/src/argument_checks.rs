@@ -0,0 +1,464 @@
+// Every argument the contracts and the claim session read, left out where it is required and passed
+// with a value of the wrong type, each time expecting the error code of that argument.
+// A failed deploy leaves the state as it was, so each test reuses one fixture.
+use super::fixture::{DeployError, Fixture, FixtureBuilder, Outcome};
+use casper_types::{runtime_args, CLValue, ContractPackageHash, Key, RuntimeArgs, U512};
+use uref_share_client::{
+    arg_errors::{self, ArgError},
+    args, entry_points, keys, locked, locked_with_share, share, Recipient, Recipients,
+};
+
+/// Copy of `args` without `name`.
+fn without(args: &RuntimeArgs, name: &str) -> RuntimeArgs {
+    let mut copy = RuntimeArgs::new();
+    for arg in args.named_args().filter(|arg| arg.name() != name) {
+        copy.insert_cl_value(arg.name(), arg.cl_value().clone());
+    }
+    copy
+}
+
+/// Copy of `args` with `name` set to the unit value, which no other type deserializes from.
+fn mistyped(args: &RuntimeArgs, name: &str) -> RuntimeArgs {
+    let mut copy = without(args, name);
+    copy.insert_cl_value(name, CLValue::from_t(()).unwrap());
+    copy
+}
+
+fn rejected(name: &str, error: ArgError) -> Outcome {
+    Err(DeployError::User(arg_errors::code(name, error).unwrap()))
+}
+
+/// Checks each of the required `names` is rejected when left out of `args`, and when mistyped.
+fn assert_required(
+    args: &RuntimeArgs,
+    names: &[&str],
+    mut call: impl FnMut(RuntimeArgs) -> Outcome,
+) {
+    for name in names {
+        assert_eq!(
+            call(without(args, name)),
+            rejected(name, ArgError::Missing),
+            "without {}",
+            name
+        );
+    }
+    assert_mistyped(args, names, call);
+}
+
+/// Checks each of `names` is rejected when mistyped in `args`.
+fn assert_mistyped(
+    args: &RuntimeArgs,
+    names: &[&str],
+    mut call: impl FnMut(RuntimeArgs) -> Outcome,
+) {
+    for name in names {
+        assert_eq!(
+            call(mistyped(args, name)),
+            rejected(name, ArgError::Invalid),
+            "mistyped {}",
+            name
+        );
+    }
+}
+
+fn standalone() -> Fixture {
+    FixtureBuilder::standalone().build()
+}
+
+#[test]
+fn codes_are_distinct_per_argument_and_error() {
+    let mut codes: Vec<u16> = arg_errors::ARGUMENTS
+        .iter()
+        .flat_map(|name| {
+            vec![
+                arg_errors::code(name, ArgError::Missing).unwrap(),
+                arg_errors::code(name, ArgError::Invalid).unwrap(),
+            ]
+        })
+        .collect();
+    for code in codes.iter() {
+        let (name, error) = arg_errors::describe(*code).unwrap();
+        assert_eq!(arg_errors::code(name, error), Some(*code));
+    }
+    codes.sort_unstable();
+    codes.dedup();
+    assert_eq!(codes.len(), 2 * arg_errors::ARGUMENTS.len());
+    assert!(codes[0] >= arg_errors::FIRST_CODE);
+    assert_eq!(arg_errors::code("unknown", ArgError::Missing), None);
+}
+
+// Sharing contract
+
+#[test]
+fn store_uref_checks_its_arguments() {
+    let mut contract = standalone();
+    let admin = contract.account("admin");
+    let uref = contract.named_uref(&admin, keys::DEPLOYER_ADMIN_ACCESS);
+    let user = contract.account("user");
+    let user_public_key = contract.public_key("user");
+    let args = share::StoreUref::new(Recipient::AccountHash(user), uref)
+        .label("checked")
+        .source_package(contract.locked_package_hash)
+        .bundle("checked")
+        .build();
+    let by_public_key = share::StoreUref::new(Recipient::PublicKey(user_public_key), uref).build();
+    let by_key = share::StoreUref::new(Recipient::Key(Key::Account(user)), uref).build();
+    let mut call = |args| contract.call_share(&admin, entry_points::STORE_UREF, args);
+
+    assert_required(&args, &[args::UREF], &mut call);
+    assert_mistyped(
+        &args,
+        &[
+            args::LABEL,
+            args::SOURCE_PACKAGE,
+            args::BUNDLE,
+            args::ACCOUNT_HASH,
+        ],
+        &mut call,
+    );
+    // Without any of the recipient arguments the contract asks for the last one, `recipient`.
+    assert_eq!(
+        call(without(&args, args::ACCOUNT_HASH)),
+        rejected(args::RECIPIENT, ArgError::Missing)
+    );
+    assert_mistyped(&by_public_key, &[args::ACCOUNT_PUBKEY], &mut call);
+    assert_mistyped(&by_key, &[args::RECIPIENT], &mut call);
+}
+
+#[test]
+fn store_uref_checks_the_purse_paying_the_fee() {
+    let mut contract = FixtureBuilder::standalone()
+        .share_args(share::InstallArgs::new().fee(U512::one(), 0).build())
+        .locked_args(runtime_args! {args::FEE_BUDGET => U512::from(10)})
+        .build();
+    let admin = contract.account("admin");
+    let uref = contract.named_uref(&admin, keys::DEPLOYER_ADMIN_ACCESS);
+    let purse = contract.main_purse(&admin);
+    let args = share::StoreUref::new(Recipient::AccountHash(contract.account("user")), uref)
+        .purse(purse)
+        .build();
+
+    assert_mistyped(&args, &[args::PURSE], |args| {
+        contract.call_share(&admin, entry_points::STORE_UREF, args)
+    });
+    // A missing purse keeps the error of a fee left unpaid.
+    assert_eq!(
+        contract.call_share(
+            &admin,
+            entry_points::STORE_UREF,
+            without(&args, args::PURSE)
+        ),
+        Err(DeployError::User(115))
+    );
+}
+
+#[test]
+fn store_uref_for_contract_checks_its_arguments() {
+    let mut contract = standalone();
+    let admin = contract.account("admin");
+    let uref = contract.named_uref(&admin, keys::DEPLOYER_ADMIN_ACCESS);
+    let args = share::StoreUrefForContract::new(contract.locked_package_hash, uref)
+        .label("checked")
+        .source_package(contract.locked_package_hash)
+        .build();
+    let mut call = |args| contract.call_share(&admin, entry_points::STORE_UREF_FOR_CONTRACT, args);
+
+    assert_required(&args, &[args::CONTRACT_PACKAGE, args::UREF], &mut call);
+    assert_mistyped(&args, &[args::LABEL, args::SOURCE_PACKAGE], &mut call);
+}
+
+#[test]
+fn store_uref_for_contract_checks_the_purse_paying_the_fee() {
+    let mut contract = FixtureBuilder::standalone()
+        .share_args(share::InstallArgs::new().fee(U512::one(), 0).build())
+        .locked_args(runtime_args! {args::FEE_BUDGET => U512::from(10)})
+        .build();
+    let admin = contract.account("admin");
+    let uref = contract.named_uref(&admin, keys::DEPLOYER_ADMIN_ACCESS);
+    let purse = contract.main_purse(&admin);
+    let args = share::StoreUrefForContract::new(contract.locked_package_hash, uref)
+        .purse(purse)
+        .build();
+
+    assert_mistyped(&args, &[args::PURSE], |args| {
+        contract.call_share(&admin, entry_points::STORE_UREF_FOR_CONTRACT, args)
+    });
+    // The fee is charged for contract recipients the same way.
+    assert_eq!(
+        contract.call_share(
+            &admin,
+            entry_points::STORE_UREF_FOR_CONTRACT,
+            without(&args, args::PURSE)
+        ),
+        Err(DeployError::User(115))
+    );
+}
+
+#[test]
+fn claims_check_their_arguments() {
+    let mut contract = standalone();
+    let user = contract.account("user");
+
+    assert_required(
+        &share::claim_urefs(None, None),
+        &[args::SOURCE_PACKAGE, args::LABEL],
+        |args| contract.call_share(&user, entry_points::CLAIM_UREFS, args),
+    );
+    assert_required(&share::claim_bundle("checked"), &[args::BUNDLE], |args| {
+        contract.call_share(&user, entry_points::CLAIM_BUNDLE, args)
+    });
+}
+
+#[test]
+fn administration_checks_its_arguments() {
+    let mut contract = standalone();
+    let admin = contract.account("admin");
+    let package_access = contract.named_uref(&admin, &keys::access_uref(keys::SHARE_CONTRACT));
+
+    assert_required(
+        &share::transfer_ownership(contract.account("user"), package_access),
+        &[args::NEW_OWNER, args::PACKAGE_ACCESS],
+        |args| contract.call_share(&admin, entry_points::TRANSFER_OWNERSHIP, args),
+    );
+    assert_required(&share::set_quotas(0, 0, 0), &args::QUOTAS, |args| {
+        contract.call_share(&admin, entry_points::SET_QUOTAS, args)
+    });
+    assert_required(
+        &share::set_fee(U512::zero(), 0),
+        &[args::FEE, args::FEE_EXPIRY],
+        |args| contract.call_share(&admin, entry_points::SET_FEE, args),
+    );
+}
+
+#[test]
+fn share_upgrade_checks_its_arguments() {
+    let mut contract = standalone();
+    let args = share::InstallArgs::new()
+        .max_delegation_depth(1)
+        .approvers(vec![contract.public_key("admin")], 1)
+        .approver_package(contract.locked_package_hash)
+        .quotas(0, 0, 0)
+        .fee(U512::zero(), 0)
+        .build();
+
+    assert_mistyped(
+        &args,
+        &[
+            args::MAX_DELEGATION_DEPTH,
+            args::APPROVERS,
+            args::APPROVAL_THRESHOLD,
+            args::APPROVER_PACKAGE,
+            args::MAX_PENDING_PER_DEPOSITOR,
+            args::FEE,
+            args::FEE_EXPIRY,
+        ],
+        |args| contract.upgrade_share(args),
+    );
+}
+
+#[test]
+fn claim_session_checks_its_arguments() {
+    let mut contract = standalone();
+    let user = contract.account("user");
+    let args = runtime_args! {
+        args::SHARE_HASH => contract.share_package_hash(),
+        args::SOURCE_PACKAGE => contract.locked_package_hash,
+        args::LABEL => String::from("checked"),
+    };
+    let mut call = |args| contract.run_claim(&user, args);
+
+    assert_required(&args, &[args::SHARE_HASH], &mut call);
+    assert_mistyped(&args, &[args::SOURCE_PACKAGE, args::LABEL], &mut call);
+}
+
+#[test]
+fn receiver_checks_its_arguments() {
+    let mut contract = FixtureBuilder::standalone().with_receiver().build();
+    let deployer = contract.deployer();
+    let args = runtime_args! {
+        args::UREF => contract.main_purse(&deployer),
+        args::LABEL => String::from("checked"),
+        args::SOURCE_PACKAGE => Option::<ContractPackageHash>::None,
+    };
+
+    assert_required(
+        &args,
+        &[args::UREF, args::LABEL, args::SOURCE_PACKAGE],
+        |args| contract.call_receiver(&deployer, entry_points::RECEIVE_UREF, args),
+    );
+    let locked_package_hash = contract.locked_package_hash;
+    assert_required(
+        &runtime_args! {args::LOCKED_PACKAGE => locked_package_hash},
+        &[args::LOCKED_PACKAGE],
+        |args| contract.call_receiver(&deployer, entry_points::CALL_LOCKED, args),
+    );
+}
+
+// Testing contract, standalone
+
+#[test]
+fn locked_entry_points_check_their_arguments() {
+    let mut contract = standalone();
+    let admin = contract.account("admin");
+    let user = contract.account("user");
+    let share_package_hash = contract.share_package_hash();
+
+    let args = locked::get_access(
+        contract.locked_package_hash,
+        Some(share_package_hash),
+        Some("checked"),
+    );
+    assert_required(&args, &[args::THIS_CONTRACT], |args| {
+        contract.call_locked_entry_point(&user, entry_points::GET_ACCESS, args)
+    });
+    assert_mistyped(&args, &[args::SHARE_CONTRACT, args::BUNDLE], |args| {
+        contract.call_locked_entry_point(&user, entry_points::GET_ACCESS, args)
+    });
+    // A URef serializes one byte longer than a package hash, so it can't pass for one either.
+    let mut uref_as_share_contract = without(&args, args::SHARE_CONTRACT);
+    uref_as_share_contract
+        .insert(args::SHARE_CONTRACT, contract.main_purse(&user))
+        .unwrap();
+    assert_eq!(
+        contract.call_locked_entry_point(&user, entry_points::GET_ACCESS, uref_as_share_contract),
+        rejected(args::SHARE_CONTRACT, ArgError::Invalid)
+    );
+    let args = locked::get_roles(contract.locked_package_hash, Some(admin));
+    assert_required(&args, &[args::THIS_CONTRACT], |args| {
+        contract.call_locked_entry_point(&user, entry_points::GET_ROLES, args)
+    });
+    assert_mistyped(&args, &[args::ACCOUNT], |args| {
+        contract.call_locked_entry_point(&user, entry_points::GET_ROLES, args)
+    });
+    assert_required(
+        &locked::top_up(user, "admin", 1),
+        &[args::ACCOUNT, args::ROLE, args::CALLS],
+        |args| contract.call_locked_entry_point(&admin, entry_points::TOP_UP, args),
+    );
+    assert_required(
+        &locked::register_guardians(vec![admin], 1),
+        &[args::GUARDIANS, args::THRESHOLD],
+        |args| contract.call_locked_entry_point(&user, entry_points::REGISTER_GUARDIANS, args),
+    );
+    assert_required(
+        &locked::declare_lost(user, admin, Some(share_package_hash), None),
+        &[args::ACCOUNT, args::NEW_ACCOUNT],
+        |args| contract.call_locked_entry_point(&admin, entry_points::DECLARE_LOST, args),
+    );
+    assert_mistyped(
+        &locked::declare_lost(
+            user,
+            admin,
+            Some(share_package_hash),
+            Some(contract.main_purse(&admin)),
+        ),
+        &[args::SHARE_CONTRACT, args::PURSE],
+        |args| contract.call_locked_entry_point(&admin, entry_points::DECLARE_LOST, args),
+    );
+}
+
+#[test]
+fn locked_install_checks_its_arguments() {
+    let mut contract = standalone();
+    let args = locked::InstallArgs::new(
+        vec![contract.public_key("user")],
+        contract.share_package_hash(),
+    )
+    .user_hashes(vec![contract.account("hash_only_user")])
+    .contracts(vec![contract.locked_package_hash])
+    .contractors(vec![contract.public_key("contractor")], 1_000, true)
+    .fee_budget(U512::one())
+    .bundle("checked")
+    .build();
+    let mut call = |args| contract.reinstall_locked(args);
+
+    assert_required(&args, &[args::USERS, args::SHARE_HASH], &mut call);
+    assert_mistyped(
+        &args,
+        &[
+            args::USER_HASHES,
+            args::CONTRACTS,
+            args::CONTRACTORS,
+            args::BUNDLE,
+            args::LEASE_DURATION,
+            args::RENEWABLE,
+            args::FEE_BUDGET,
+        ],
+        &mut call,
+    );
+}
+
+#[test]
+fn locked_install_checks_allowances() {
+    // Allowances are read once the admin group exists, so only a first install gets to them.
+    let installed = FixtureBuilder::standalone()
+        .locked_args(runtime_args! {args::ALLOWANCES => ()})
+        .try_build();
+    assert_eq!(
+        installed.err(),
+        rejected(args::ALLOWANCES, ArgError::Invalid).err()
+    );
+}
+
+// Testing contract, integrated
+
+#[test]
+fn integrated_entry_points_check_their_arguments() {
+    let mut contract = FixtureBuilder::integrated().build();
+    let admin = contract.account("admin");
+    let user = contract.account("user");
+    let uref = contract.named_uref(&admin, keys::DEPLOYER_ADMIN_ACCESS);
+    let append = |contract: &mut Fixture, args| {
+        contract.call_locked_entry_point(&admin, entry_points::APPEND_UREFS, args)
+    };
+
+    let args = locked_with_share::append_urefs(vec![uref], Recipients::AccountHashes(vec![user]));
+    assert_required(&args, &[args::UREFS], |args| append(&mut contract, args));
+    assert_mistyped(&args, &[args::ACCOUNT_HASHES], |args| {
+        append(&mut contract, args)
+    });
+    assert_eq!(
+        append(&mut contract, without(&args, args::ACCOUNT_HASHES)),
+        rejected(args::RECIPIENTS, ArgError::Missing)
+    );
+    let by_public_keys = locked_with_share::append_urefs(
+        vec![uref],
+        Recipients::PublicKeys(vec![contract.public_key("user")]),
+    );
+    assert_mistyped(&by_public_keys, &[args::ACCOUNT_PUBKEYS], |args| {
+        append(&mut contract, args)
+    });
+    let by_keys =
+        locked_with_share::append_urefs(vec![uref], Recipients::Keys(vec![Key::Account(user)]));
+    assert_mistyped(&by_keys, &[args::RECIPIENTS], |args| {
+        append(&mut contract, args)
+    });
+
+    let locked_package_hash = contract.locked_package_hash;
+    assert_required(
+        &locked_with_share::get_access(locked_package_hash),
+        &[args::THIS_CONTRACT],
+        |args| contract.call_locked_entry_point(&user, entry_points::GET_ACCESS, args),
+    );
+    assert_required(
+        &locked_with_share::request_access("admin", "checked"),
+        &[args::ROLE, args::NOTE],
+        |args| contract.call_locked_entry_point(&user, entry_points::REQUEST_ACCESS, args),
+    );
+}
+
+#[test]
+fn integrated_install_checks_its_arguments() {
+    let mut contract = FixtureBuilder::integrated().build();
+    let args = locked_with_share::InstallArgs::new(vec![contract.public_key("user")]).build();
+    assert_required(&args, &[args::USERS], |args| {
+        contract.reinstall_locked(args)
+    });
+
+    for name in &[args::APPROVAL_THRESHOLD, args::ADMIN_APPROVERS] {
+        let installed = FixtureBuilder::integrated()
+            .locked_args(mistyped(&RuntimeArgs::new(), name))
+            .try_build();
+        assert_eq!(installed.err(), rejected(name, ArgError::Invalid).err());
+    }
+}
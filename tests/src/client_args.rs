@@ -0,0 +1,389 @@
+// The argument builders and return value decoders of the client crate, against the entry points
+// the contracts declare. Every argument a builder passes has to be declared with the type it is
+// passed as, and together the calls of an entry point have to pass each of its parameters.
+// Install arguments are not part of the entry points, `argument_checks` covers them.
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::fixture::account_key;
+use casper_types::{
+    account::AccountHash, AccessRights, CLType, CLValue, ContractPackageHash, EntryPoints, Key,
+    RuntimeArgs, URef, U512,
+};
+use uref_share_client::{
+    decode, entry_points, locked, locked_with_share, schema, share, Entry, Recipient, Recipients,
+};
+
+/// Parameters and return type of one entry point.
+struct Declared {
+    parameters: BTreeMap<String, CLType>,
+    ret: CLType,
+}
+
+fn declared(entry_points: EntryPoints) -> BTreeMap<String, Declared> {
+    entry_points
+        .take_entry_points()
+        .into_iter()
+        .map(|entry_point| {
+            let parameters = entry_point
+                .args()
+                .iter()
+                .map(|parameter| (parameter.name().to_string(), parameter.cl_type().clone()))
+                .collect();
+            let declared = Declared {
+                parameters,
+                ret: entry_point.ret().clone(),
+            };
+            (entry_point.name().to_string(), declared)
+        })
+        .collect()
+}
+
+/// Checks the `calls` built for each entry point of `entry_points`, but for the ones
+/// listed as `without_builder`, which the client crate has no arguments for.
+fn assert_builders_match(
+    entry_points: EntryPoints,
+    calls: Vec<(&str, Vec<RuntimeArgs>)>,
+    without_builder: &[&str],
+) {
+    let declared = declared(entry_points);
+    let mut checked: BTreeSet<String> = without_builder
+        .iter()
+        .map(|name| name.to_string())
+        .collect();
+    for (entry_point, built) in calls {
+        let parameters = &declared
+            .get(entry_point)
+            .unwrap_or_else(|| panic!("{} is not declared", entry_point))
+            .parameters;
+        let mut passed = BTreeSet::new();
+        for args in built.iter() {
+            for arg in args.named_args() {
+                assert_eq!(
+                    parameters.get(arg.name()),
+                    Some(arg.cl_value().cl_type()),
+                    "{} passes {}",
+                    entry_point,
+                    arg.name()
+                );
+                passed.insert(arg.name().to_string());
+            }
+        }
+        let names: BTreeSet<String> = parameters.keys().cloned().collect();
+        assert_eq!(passed, names, "arguments of {}", entry_point);
+        checked.insert(entry_point.to_string());
+    }
+    let names: BTreeSet<String> = declared.keys().cloned().collect();
+    assert_eq!(checked, names, "entry points checked");
+}
+
+/// Checks `value` has the type `entry_point` declares it returns.
+fn assert_returns(entry_points: EntryPoints, entry_point: &str, value: &CLValue) {
+    let declared = declared(entry_points);
+    let ret = &declared
+        .get(entry_point)
+        .unwrap_or_else(|| panic!("{} is not declared", entry_point))
+        .ret;
+    assert_eq!(ret, value.cl_type(), "return type of {}", entry_point);
+}
+
+fn uref() -> URef {
+    URef::new([1; 32], AccessRights::READ_ADD_WRITE)
+}
+
+fn package() -> ContractPackageHash {
+    ContractPackageHash::new([2; 32])
+}
+
+fn account() -> AccountHash {
+    AccountHash::new([3; 32])
+}
+
+/// Each way of naming a recipient.
+fn recipients() -> Vec<Recipient> {
+    vec![
+        Recipient::PublicKey(account_key(4)),
+        Recipient::AccountHash(account()),
+        Recipient::Key(Key::Account(account())),
+    ]
+}
+
+/// `build` called with each way of naming a recipient.
+fn for_recipients(build: impl Fn(Recipient) -> RuntimeArgs) -> Vec<RuntimeArgs> {
+    recipients().into_iter().map(build).collect()
+}
+
+#[test]
+fn share_builders_match_the_schema() {
+    assert_builders_match(
+        schema::share(),
+        vec![
+            (entry_points::RETRIEVE_UREF, vec![share::retrieve_uref()]),
+            (
+                entry_points::CLAIM_UREFS,
+                vec![share::claim_urefs(Some(package()), Some("label"))],
+            ),
+            (
+                entry_points::CLAIM_BUNDLE,
+                vec![share::claim_bundle("bundle")],
+            ),
+            (
+                entry_points::STORE_UREF,
+                for_recipients(|recipient| {
+                    share::StoreUref::new(recipient, uref())
+                        .label("label")
+                        .source_package(package())
+                        .purse(uref())
+                        .bundle("bundle")
+                        .build()
+                }),
+            ),
+            (
+                entry_points::STORE_UREF_FOR_CONTRACT,
+                vec![share::StoreUrefForContract::new(package(), uref())
+                    .label("label")
+                    .source_package(package())
+                    .purse(uref())
+                    .build()],
+            ),
+            (entry_points::DELEGATE, {
+                let mut calls = for_recipients(|recipient| share::delegate(uref(), recipient));
+                calls.push(share::delegate_with_purse(
+                    uref(),
+                    Recipient::AccountHash(account()),
+                    uref(),
+                ));
+                calls
+            }),
+            (entry_points::APPROVE_RELEASE, {
+                let mut calls = for_recipients(share::approve_release);
+                calls.push(share::approve_release_with_uref(
+                    Recipient::AccountHash(account()),
+                    uref(),
+                ));
+                calls
+            }),
+            (
+                entry_points::REVOKE_DELEGATION,
+                for_recipients(share::revoke_delegation),
+            ),
+            (entry_points::PAUSE, vec![share::pause()]),
+            (entry_points::UNPAUSE, vec![share::unpause()]),
+            (
+                entry_points::TRANSFER_OWNERSHIP,
+                vec![share::transfer_ownership(account(), uref())],
+            ),
+            (entry_points::SET_QUOTAS, vec![share::set_quotas(1, 2, 3)]),
+            (entry_points::SET_FEE, vec![share::set_fee(U512::one(), 1)]),
+            (
+                entry_points::RECLAIM_UREF,
+                for_recipients(|recipient| {
+                    share::ReclaimUref::new(recipient)
+                        .label("label")
+                        .source_package(package())
+                        .build()
+                }),
+            ),
+            (
+                entry_points::EXPIRE_ENTRIES,
+                for_recipients(share::expire_entries),
+            ),
+            (entry_points::GET_USAGE, for_recipients(share::get_usage)),
+            (
+                entry_points::ACCEPT_OWNERSHIP,
+                vec![share::accept_ownership(uref())],
+            ),
+        ],
+        &[entry_points::VERSION, entry_points::PUSHED_ENTRY],
+    );
+}
+
+#[test]
+fn locked_builders_match_the_schema() {
+    assert_builders_match(
+        schema::locked(),
+        vec![
+            (entry_points::GROUP_ACCESS_ONLY, vec![locked::no_args()]),
+            (
+                entry_points::GET_ACCESS,
+                vec![locked::get_access(
+                    package(),
+                    Some(package()),
+                    Some("bundle"),
+                )],
+            ),
+            (entry_points::CHECK_ACCESS, vec![locked::no_args()]),
+            (entry_points::ROLES_OF, vec![locked::roles_of(account())]),
+            (
+                entry_points::GET_ROLES,
+                vec![locked::get_roles(package(), Some(account()))],
+            ),
+            (entry_points::METERED_ACCESS, vec![locked::no_args()]),
+            (
+                entry_points::SET_ALLOWANCE,
+                vec![locked::set_allowance("admin", 1)],
+            ),
+            (
+                entry_points::TOP_UP,
+                vec![locked::top_up(account(), "admin", 1)],
+            ),
+            (
+                entry_points::RESET_USAGE,
+                vec![locked::reset_usage(account(), "admin")],
+            ),
+            (
+                entry_points::REGISTER_GUARDIANS,
+                vec![locked::register_guardians(vec![account()], 1)],
+            ),
+            (
+                entry_points::DECLARE_LOST,
+                vec![locked::declare_lost(
+                    account(),
+                    account(),
+                    Some(package()),
+                    Some(uref()),
+                )],
+            ),
+            (entry_points::SWEEP_EXPIRED, vec![locked::no_args()]),
+            (entry_points::RENEW, vec![locked::no_args()]),
+            (entry_points::SHARE_BINDING, vec![locked::no_args()]),
+        ],
+        &[
+            entry_points::CONFIRM_DEPOSIT,
+            entry_points::ISSUE_DELEGATE_UREF,
+            entry_points::REVOKE_DELEGATE_UREF,
+        ],
+    );
+}
+
+#[test]
+fn locked_with_share_builders_match_the_schema() {
+    let append_urefs = |recipients| locked_with_share::append_urefs(vec![uref()], recipients);
+    assert_builders_match(
+        schema::locked_with_share(),
+        vec![
+            (
+                entry_points::GROUP_ACCESS_ONLY,
+                vec![locked_with_share::no_args()],
+            ),
+            (
+                entry_points::GET_ACCESS,
+                vec![locked_with_share::get_access(package())],
+            ),
+            (
+                entry_points::RETRIEVE_UREFS,
+                vec![locked_with_share::no_args()],
+            ),
+            (
+                entry_points::APPEND_UREFS,
+                vec![
+                    append_urefs(Recipients::PublicKeys(vec![account_key(4)])),
+                    append_urefs(Recipients::AccountHashes(vec![account()])),
+                    append_urefs(Recipients::Keys(vec![Key::Account(account())])),
+                ],
+            ),
+            (entry_points::APPROVE_RELEASE, {
+                let mut calls = for_recipients(locked_with_share::approve_release);
+                calls.push(locked_with_share::approve_release_with_uref(
+                    Recipient::AccountHash(account()),
+                    uref(),
+                ));
+                calls
+            }),
+            (
+                entry_points::REQUEST_ACCESS,
+                vec![locked_with_share::request_access("admin", "note")],
+            ),
+            (
+                entry_points::LIST_ACCESS_REQUESTS,
+                vec![locked_with_share::no_args()],
+            ),
+            (
+                entry_points::GET_ACCESS_REQUESTS,
+                vec![locked_with_share::get_access_requests(package())],
+            ),
+            (
+                entry_points::APPROVE_ACCESS,
+                vec![locked_with_share::approve_access(account())],
+            ),
+            (
+                entry_points::DENY_ACCESS,
+                vec![locked_with_share::deny_access(account())],
+            ),
+            (entry_points::PAUSE, vec![locked_with_share::no_args()]),
+            (entry_points::UNPAUSE, vec![locked_with_share::no_args()]),
+            (
+                entry_points::TRANSFER_OWNERSHIP,
+                vec![locked_with_share::transfer_ownership(account())],
+            ),
+            (
+                entry_points::ACCEPT_OWNERSHIP,
+                vec![locked_with_share::no_args()],
+            ),
+        ],
+        &[entry_points::CONFIRM_APPROVER],
+    );
+}
+
+#[test]
+fn decoders_round_trip_the_declared_return_values() {
+    let entry: Entry = (Some(package()), "label".to_string(), uref());
+
+    let value = CLValue::from_t(uref()).unwrap();
+    assert_returns(schema::share(), entry_points::RETRIEVE_UREF, &value);
+    assert_eq!(decode::uref(value).unwrap(), uref());
+
+    let value = CLValue::from_t(vec![uref()]).unwrap();
+    assert_returns(
+        schema::locked_with_share(),
+        entry_points::RETRIEVE_UREFS,
+        &value,
+    );
+    assert_eq!(decode::urefs(value).unwrap(), vec![uref()]);
+
+    let entries = vec![entry.clone(), (None, "admin".to_string(), uref())];
+    let value = CLValue::from_t(entries.clone()).unwrap();
+    assert_returns(schema::share(), entry_points::CLAIM_UREFS, &value);
+    assert_returns(schema::share(), entry_points::CLAIM_BUNDLE, &value);
+    assert_eq!(decode::entries(value).unwrap(), entries);
+
+    let usage: decode::Usage = (1, 2, 3);
+    let value = CLValue::from_t(usage).unwrap();
+    assert_returns(schema::share(), entry_points::GET_USAGE, &value);
+    assert_eq!(decode::usage(value).unwrap(), usage);
+
+    let roles = vec!["admin".to_string(), "deployer".to_string()];
+    let value = CLValue::from_t(roles.clone()).unwrap();
+    assert_returns(schema::locked(), entry_points::CHECK_ACCESS, &value);
+    assert_returns(schema::locked(), entry_points::ROLES_OF, &value);
+    assert_eq!(decode::roles(value).unwrap(), roles);
+
+    for push in vec![Some((package(), entry)), None] {
+        let value = CLValue::from_t(push.clone()).unwrap();
+        assert_returns(schema::share(), entry_points::PUSHED_ENTRY, &value);
+        assert_eq!(decode::pushed_entry(value).unwrap(), push);
+    }
+
+    for binding in vec![(package(), Some(2)), (package(), None)] {
+        let value = CLValue::from_t(binding).unwrap();
+        assert_returns(schema::locked(), entry_points::SHARE_BINDING, &value);
+        assert_eq!(decode::share_binding(value).unwrap(), binding);
+    }
+
+    let requests: Vec<decode::AccessRequest> =
+        vec![(account(), "admin".to_string(), "note".to_string())];
+    let value = CLValue::from_t(requests.clone()).unwrap();
+    assert_returns(
+        schema::locked_with_share(),
+        entry_points::LIST_ACCESS_REQUESTS,
+        &value,
+    );
+    assert_eq!(decode::access_requests(value).unwrap(), requests);
+}
+
+#[test]
+fn decoders_reject_other_types() {
+    // A value of another type is an error rather than garbage.
+    let value = CLValue::from_t(true).unwrap();
+    assert!(decode::roles(value.clone()).is_err());
+    assert!(decode::uref(value).is_err());
+}
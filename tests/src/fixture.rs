@@ -0,0 +1,766 @@
+#![allow(unused)]
+use std::{collections::BTreeMap, convert::TryFrom};
+
+use casper_engine_test_support::{
+    internal::{utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS},
+    Hash,
+};
+use casper_execution_engine::{
+    core::{engine_state, engine_state::genesis::GenesisAccount, execution},
+    shared::motes::Motes,
+};
+use casper_types::{
+    account::AccountHash, bytesrepr::FromBytes, contracts::ContractVersion, runtime_args,
+    AccessRights, ApiError, CLTyped, CLValue, ContractHash, ContractPackageHash, Key, PublicKey,
+    RuntimeArgs, SecretKey, URef, U512,
+};
+use uref_share_client::{
+    args, decode::AccessRequest, entry_points, keys, locked, locked_with_share, share, Recipient,
+    Recipients, DEFAULT_LABEL,
+};
+
+/// Motes every test account starts with.
+const INITIAL_BALANCE: u64 = 500_000_000_000_000_000;
+
+/// Public key of an ed25519 test account. Accounts added to a `FixtureBuilder` are seeded
+/// by their position, the first one with `1`.
+pub fn account_key(seed: u8) -> PublicKey {
+    SecretKey::ed25519_from_bytes([seed; 32]).unwrap().into()
+}
+
+/// Public key of a secp256k1 test account.
+pub fn secp256k1_account_key(seed: u8) -> PublicKey {
+    SecretKey::secp256k1_from_bytes([seed; 32]).unwrap().into()
+}
+
+/// Edition of the example that gets deployed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Mode {
+    /// `locked.wasm`, sharing its URefs through `uref-share.wasm`.
+    Standalone,
+    /// `locked_with_share.wasm`, storing its URefs itself.
+    Integrated,
+}
+
+/// How an account takes part in the deployment.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Role {
+    /// Installs the contracts and holds the `locked_deployer_admin_access` URef.
+    Deployer,
+    /// Receives an `admin` URef on install, named by its public key.
+    Holder,
+    /// Receives an `admin` URef on install, named by its account hash only. Standalone only.
+    HashHolder,
+    /// Receives a leased `admin` URef on install. Standalone only.
+    Contractor,
+    /// Receives nothing.
+    Outsider,
+}
+
+/// Why a deploy failed, decoded from the engine error.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DeployError {
+    /// The contract reverted with `ApiError::User(code)`.
+    User(u16),
+    /// The caller lacks the group URef the entry point requires.
+    InvalidContext,
+    /// The called contract package has no such version.
+    InvalidContractVersion(ContractVersion),
+    /// Any other failure, with the message of the engine.
+    Other(String),
+}
+
+impl From<engine_state::Error> for DeployError {
+    fn from(error: engine_state::Error) -> Self {
+        match error {
+            engine_state::Error::Exec(execution::Error::Revert(ApiError::User(code))) => {
+                DeployError::User(code)
+            }
+            engine_state::Error::Exec(execution::Error::InvalidContext) => {
+                DeployError::InvalidContext
+            }
+            engine_state::Error::Exec(execution::Error::InvalidContractVersion(version)) => {
+                DeployError::InvalidContractVersion(version.contract_version())
+            }
+            error => DeployError::Other(format!("{:?}", error)),
+        }
+    }
+}
+
+/// Result of a deploy. Failed deploys are not committed, so the fixture can be used further.
+pub type Outcome = Result<(), DeployError>;
+
+struct Account {
+    name: String,
+    public_key: PublicKey,
+    role: Role,
+}
+
+/// Collects the accounts, their roles and the install arguments, then deploys with `build`.
+pub struct FixtureBuilder {
+    mode: Mode,
+    accounts: Vec<Account>,
+    share_args: RuntimeArgs,
+    locked_args: RuntimeArgs,
+    with_receiver: bool,
+}
+
+impl FixtureBuilder {
+    /// Starts an empty deployment, add at least the `Deployer` before building.
+    pub fn new(mode: Mode) -> Self {
+        Self {
+            mode,
+            accounts: Vec::new(),
+            share_args: RuntimeArgs::new(),
+            locked_args: RuntimeArgs::new(),
+            with_receiver: false,
+        }
+    }
+
+    /// The standalone accounts most tests use: `admin` deploys, `user`, `secp_user` and
+    /// `hash_only_user` receive access, `unauth` and `contractor` do not.
+    pub fn standalone() -> Self {
+        Self::new(Mode::Standalone)
+            .user("admin", Role::Deployer)
+            .user("user", Role::Holder)
+            .user("unauth", Role::Outsider)
+            .secp256k1_user("secp_user", Role::Holder)
+            .user("hash_only_user", Role::HashHolder)
+            .user("contractor", Role::Outsider)
+    }
+
+    /// The integrated accounts most tests use: `admin` deploys, `user` receives access, `unauth` does not.
+    pub fn integrated() -> Self {
+        Self::new(Mode::Integrated)
+            .user("admin", Role::Deployer)
+            .user("user", Role::Holder)
+            .user("unauth", Role::Outsider)
+    }
+
+    /// Adds an ed25519 account.
+    pub fn user(self, name: &str, role: Role) -> Self {
+        let seed = self.next_seed();
+        self.with_account(name, account_key(seed), role)
+    }
+
+    /// Adds a secp256k1 account.
+    pub fn secp256k1_user(self, name: &str, role: Role) -> Self {
+        let seed = self.next_seed();
+        self.with_account(name, secp256k1_account_key(seed), role)
+    }
+
+    /// Changes the role of an account added earlier.
+    pub fn role(mut self, name: &str, role: Role) -> Self {
+        self.accounts
+            .iter_mut()
+            .find(|account| account.name == name)
+            .unwrap_or_else(|| panic!("no account named {}", name))
+            .role = role;
+        self
+    }
+
+    /// Install arguments of the URef sharing contract.
+    pub fn share_args(mut self, args: RuntimeArgs) -> Self {
+        self.share_args = args;
+        self
+    }
+
+    /// Install arguments of the testing contract, on top of the ones derived from the roles.
+    pub fn locked_args(mut self, args: RuntimeArgs) -> Self {
+        self.locked_args = args;
+        self
+    }
+
+    /// Also installs the example receiver contract, which gets an access URef as well.
+    pub fn with_receiver(mut self) -> Self {
+        self.with_receiver = true;
+        self
+    }
+
+    /// Deploys the contracts onto a fresh context, panicking if any install fails.
+    pub fn build(self) -> Fixture {
+        self.try_build()
+            .unwrap_or_else(|error| panic!("install failed: {:?}", error))
+    }
+
+    /// Same as `build`, but returns the error of a failing install.
+    pub fn try_build(self) -> Result<Fixture, DeployError> {
+        let mut genesis_accounts = DEFAULT_ACCOUNTS.clone();
+        for account in self.accounts.iter() {
+            genesis_accounts.push(GenesisAccount::account(
+                account.public_key.clone(),
+                Motes::new(U512::from(INITIAL_BALANCE)),
+                None,
+            ));
+        }
+        let mut builder = InMemoryWasmTestBuilder::default();
+        builder
+            .run_genesis(&utils::create_run_genesis_request(genesis_accounts))
+            .commit();
+
+        let deployer = self
+            .accounts_with(Role::Deployer)
+            .first()
+            .map(AccountHash::from)
+            .unwrap_or_else(|| panic!("no account with the Deployer role"));
+        let holders = self.accounts_with(Role::Holder);
+        let hash_holders: Vec<AccountHash> = self
+            .accounts_with(Role::HashHolder)
+            .iter()
+            .map(AccountHash::from)
+            .collect();
+        let contractors = self.accounts_with(Role::Contractor);
+
+        let mut fixture = Fixture {
+            builder,
+            block_time: 0,
+            mode: self.mode,
+            locked_hash: Hash::default(),
+            locked_package_hash: ContractPackageHash::default(),
+            share: None,
+            receiver_hash: None,
+            receiver_package_hash: None,
+            deployer,
+            accounts: self
+                .accounts
+                .iter()
+                .map(|account| {
+                    let hash = AccountHash::from(&account.public_key);
+                    (account.name.clone(), (hash, account.public_key.clone()))
+                })
+                .collect(),
+        };
+
+        let mut locked_args = runtime_args! {args::USERS => holders};
+        match self.mode {
+            Mode::Standalone => {
+                // Deploy the URef sharing contract onto the context.
+                fixture.run_wasm(&deployer, "uref-share.wasm", self.share_args)?;
+                let share_package_hash: ContractPackageHash =
+                    fixture.query(&[&keys::wrapped_package_hash(keys::SHARE_CONTRACT)]);
+                let share_hash: Hash = fixture.query(&[&keys::wrapped(keys::SHARE_CONTRACT)]);
+                fixture.share = Some((share_hash, share_package_hash));
+
+                locked_args.insert(args::USER_HASHES, hash_holders).unwrap();
+                locked_args
+                    .insert(args::SHARE_HASH, share_package_hash)
+                    .unwrap();
+                if !contractors.is_empty() {
+                    locked_args.insert(args::CONTRACTORS, contractors).unwrap();
+                }
+                if self.with_receiver {
+                    // Deploy the receiver contract onto the context.
+                    fixture.run_wasm(
+                        &deployer,
+                        "receiver.wasm",
+                        runtime_args! {args::SHARE_HASH => share_package_hash},
+                    )?;
+                    let receiver_package_hash: ContractPackageHash =
+                        fixture.query(&[&keys::wrapped_package_hash(keys::RECEIVER_CONTRACT)]);
+                    fixture.receiver_hash =
+                        Some(fixture.query(&[&keys::wrapped(keys::RECEIVER_CONTRACT)]));
+                    fixture.receiver_package_hash = Some(receiver_package_hash);
+                    locked_args
+                        .insert(args::CONTRACTS, vec![receiver_package_hash])
+                        .unwrap();
+                }
+                for named_arg in self.locked_args.named_args() {
+                    locked_args.insert_cl_value(named_arg.name(), named_arg.cl_value().clone());
+                }
+
+                // Get the testing contract onto the context
+                fixture.run_wasm(&deployer, "locked.wasm", locked_args)?;
+                fixture.locked_hash = fixture.query(&[&keys::wrapped(keys::LOCKED_CONTRACT)]);
+                fixture.locked_package_hash =
+                    fixture.query(&[&keys::wrapped_package_hash(keys::LOCKED_CONTRACT)]);
+            }
+            Mode::Integrated => {
+                assert!(
+                    hash_holders.is_empty() && contractors.is_empty() && !self.with_receiver,
+                    "the integrated edition only shares URefs with Holder accounts"
+                );
+                for named_arg in self.locked_args.named_args() {
+                    locked_args.insert_cl_value(named_arg.name(), named_arg.cl_value().clone());
+                }
+
+                // Deploy the contract storing its own URefs onto the context.
+                fixture.run_wasm(&deployer, "locked_with_share.wasm", locked_args)?;
+                fixture.locked_hash =
+                    fixture.query(&[&keys::wrapped_hash(keys::LOCKED_WITH_SHARE_CONTRACT)]);
+                fixture.locked_package_hash = fixture.query(&[&keys::wrapped_package_hash(
+                    keys::LOCKED_WITH_SHARE_CONTRACT,
+                )]);
+            }
+        }
+        Ok(fixture)
+    }
+
+    fn next_seed(&self) -> u8 {
+        self.accounts.len() as u8 + 1
+    }
+
+    fn with_account(mut self, name: &str, public_key: PublicKey, role: Role) -> Self {
+        self.accounts.push(Account {
+            name: name.to_string(),
+            public_key,
+            role,
+        });
+        self
+    }
+
+    fn accounts_with(&self, role: Role) -> Vec<PublicKey> {
+        self.accounts
+            .iter()
+            .filter(|account| account.role == role)
+            .map(|account| account.public_key.clone())
+            .collect()
+    }
+}
+
+/// The deployed contracts and the accounts taking part, with helpers for the common calls.
+/// Calls return the `Outcome` of the deploy, helpers that only make sense for one edition
+/// panic in the other.
+pub struct Fixture {
+    pub builder: InMemoryWasmTestBuilder,
+    pub mode: Mode,
+    pub locked_hash: Hash,
+    pub locked_package_hash: ContractPackageHash,
+    pub receiver_hash: Option<Hash>,
+    pub receiver_package_hash: Option<ContractPackageHash>,
+    share: Option<(Hash, ContractPackageHash)>,
+    deployer: AccountHash,
+    accounts: BTreeMap<String, (AccountHash, PublicKey)>,
+    block_time: u64,
+}
+
+impl Fixture {
+    /// Account hash of the account added as `name`.
+    pub fn account(&self, name: &str) -> AccountHash {
+        self.named_account(name).0
+    }
+
+    /// Public key of the account added as `name`.
+    pub fn public_key(&self, name: &str) -> PublicKey {
+        self.named_account(name).1.clone()
+    }
+
+    /// The account that installed the contracts.
+    pub fn deployer(&self) -> AccountHash {
+        self.deployer
+    }
+
+    /// Contract hash of the URef sharing contract.
+    pub fn share_hash(&self) -> Hash {
+        self.share().0
+    }
+
+    /// Package hash of the URef sharing contract.
+    pub fn share_package_hash(&self) -> ContractPackageHash {
+        self.share().1
+    }
+
+    /// Sets the block time, in milliseconds, the following deploys run at.
+    pub fn set_block_time(&mut self, block_time: u64) {
+        self.block_time = block_time;
+    }
+
+    // Access
+
+    /// Call the access restricted function on the testing contract.
+    pub fn call_protected(&mut self, caller: &AccountHash) -> Outcome {
+        self.call_locked_entry_point(caller, entry_points::GROUP_ACCESS_ONLY, locked::no_args())
+    }
+
+    /// Call the function that gets the caller rights to call the access restricted function.
+    pub fn claim_access(&mut self, caller: &AccountHash) -> Outcome {
+        let args = match self.mode {
+            Mode::Standalone => locked::get_access(self.locked_package_hash, None, None),
+            Mode::Integrated => locked_with_share::get_access(self.locked_package_hash),
+        };
+        self.call_locked_entry_point(caller, entry_points::GET_ACCESS, args)
+    }
+
+    /// Same as `claim_access`, but claims every URef deposited for the caller as part of `bundle`.
+    pub fn claim_bundle_access(&mut self, caller: &AccountHash, bundle: &str) -> Outcome {
+        let args = locked::get_access(self.locked_package_hash, None, Some(bundle));
+        self.call_locked_entry_point(caller, entry_points::GET_ACCESS, args)
+    }
+
+    /// Claim every pending URef with the standalone `claim.wasm` session,
+    /// optionally only the ones from `source_package` or stored under `label`.
+    pub fn claim(
+        &mut self,
+        caller: &AccountHash,
+        source_package: Option<ContractPackageHash>,
+        label: Option<&str>,
+    ) -> Outcome {
+        let mut args = runtime_args! {args::SHARE_HASH => self.share_package_hash()};
+        if let Some(source_package) = source_package {
+            args.insert(args::SOURCE_PACKAGE, source_package).unwrap();
+        }
+        if let Some(label) = label {
+            args.insert(args::LABEL, label.to_string()).unwrap();
+        }
+        self.run_claim(caller, args)
+    }
+
+    /// Run the standalone `claim.wasm` session with `args` as they are.
+    pub fn run_claim(&mut self, caller: &AccountHash, args: RuntimeArgs) -> Outcome {
+        self.run_wasm(caller, "claim.wasm", args)
+    }
+
+    /// Make the receiver contract call the access restricted function with its own URefs.
+    /// Only its installer holds the `operator` URef needed for that.
+    pub fn receiver_call_locked(&mut self, caller: &AccountHash) -> Outcome {
+        let args = runtime_args! {args::LOCKED_PACKAGE => self.locked_package_hash};
+        self.call_receiver(caller, entry_points::CALL_LOCKED, args)
+    }
+
+    // Deposits
+
+    /// Share the URef `caller` holds under `uref_name` with `recipient`, through the sharing
+    /// contract or, integrated, the testing contract itself. Integrated recipients must be accounts.
+    pub fn deposit(&mut self, caller: &AccountHash, uref_name: &str, recipient: Key) -> Outcome {
+        let uref = self.named_uref(caller, uref_name);
+        match self.mode {
+            Mode::Standalone => self.call_share(
+                caller,
+                entry_points::STORE_UREF,
+                share::StoreUref::new(Recipient::Key(recipient), uref).build(),
+            ),
+            Mode::Integrated => {
+                let account_hash = recipient
+                    .into_account()
+                    .unwrap_or_else(|| panic!("integrated recipients must be accounts"));
+                self.call_locked_entry_point(
+                    caller,
+                    entry_points::APPEND_UREFS,
+                    locked_with_share::append_urefs(
+                        vec![uref],
+                        Recipients::AccountHashes(vec![account_hash]),
+                    ),
+                )
+            }
+        }
+    }
+
+    /// Same as `deposit`, paying the deposit fee from the main purse of `caller`. Standalone only.
+    pub fn deposit_with_fee(
+        &mut self,
+        caller: &AccountHash,
+        uref_name: &str,
+        recipient: Key,
+    ) -> Outcome {
+        let uref = self.named_uref(caller, uref_name);
+        let purse = self.main_purse(caller);
+        self.call_share(
+            caller,
+            entry_points::STORE_UREF,
+            share::StoreUref::new(Recipient::Key(recipient), uref)
+                .purse(purse)
+                .build(),
+        )
+    }
+
+    /// Store `uref` for `recipient` in the sharing contract, tagged with `source_package`.
+    /// Standalone only.
+    pub fn deposit_from_package(
+        &mut self,
+        caller: &AccountHash,
+        uref: URef,
+        recipient: Key,
+        source_package: ContractPackageHash,
+    ) -> Outcome {
+        self.call_share(
+            caller,
+            entry_points::STORE_UREF,
+            share::StoreUref::new(Recipient::Key(recipient), uref)
+                .source_package(source_package)
+                .build(),
+        )
+    }
+
+    /// Delegate the `admin` URef held by `caller` to `recipient` through the sharing contract.
+    pub fn delegate(&mut self, caller: &AccountHash, recipient: &PublicKey) -> Outcome {
+        let uref = self.held_uref(caller);
+        self.call_share(
+            caller,
+            entry_points::DELEGATE,
+            share::delegate(uref, Recipient::PublicKey(recipient.clone())),
+        )
+    }
+
+    /// Same as `delegate`, paying the deposit fee from the callers main purse.
+    pub fn delegate_with_fee(&mut self, caller: &AccountHash, recipient: &PublicKey) -> Outcome {
+        let uref = self.held_uref(caller);
+        let purse = self.main_purse(caller);
+        self.call_share(
+            caller,
+            entry_points::DELEGATE,
+            share::delegate_with_purse(uref, Recipient::PublicKey(recipient.clone()), purse),
+        )
+    }
+
+    /// Revoke the delegation to `target`, and everything delegated onwards from it.
+    pub fn revoke(&mut self, caller: &AccountHash, target: &PublicKey) -> Outcome {
+        self.call_share(
+            caller,
+            entry_points::REVOKE_DELEGATION,
+            share::revoke_delegation(Recipient::PublicKey(target.clone())),
+        )
+    }
+
+    /// Approve the release of the URefs pending for `recipient`, in whichever contract stores them.
+    pub fn approve_release(&mut self, caller: &AccountHash, recipient: &PublicKey) -> Outcome {
+        let recipient = Recipient::PublicKey(recipient.clone());
+        match self.mode {
+            Mode::Standalone => self.call_share(
+                caller,
+                entry_points::APPROVE_RELEASE,
+                share::approve_release(recipient),
+            ),
+            Mode::Integrated => self.call_locked_entry_point(
+                caller,
+                entry_points::APPROVE_RELEASE,
+                locked_with_share::approve_release(recipient),
+            ),
+        }
+    }
+
+    // Access requests, integrated only
+
+    /// Ask for a URef of the `role` group.
+    pub fn request_access(&mut self, caller: &AccountHash, role: &str) -> Outcome {
+        self.request_access_with_note(caller, role, "test request")
+    }
+
+    /// Ask for a URef of the `role` group, explaining why in `note`.
+    pub fn request_access_with_note(
+        &mut self,
+        caller: &AccountHash,
+        role: &str,
+        note: &str,
+    ) -> Outcome {
+        let args = locked_with_share::request_access(role, note);
+        self.call_locked_entry_point(caller, entry_points::REQUEST_ACCESS, args)
+    }
+
+    /// The pending requests, as `caller` reads them through `get_access_requests`.
+    pub fn access_requests(
+        &mut self,
+        caller: &AccountHash,
+    ) -> Result<Vec<AccessRequest>, DeployError> {
+        let args = locked_with_share::get_access_requests(self.locked_package_hash);
+        self.call_locked_entry_point(caller, entry_points::GET_ACCESS_REQUESTS, args)?;
+        Ok(self.query_account(caller, &[keys::ACCESS_REQUESTS]))
+    }
+
+    /// The roles of `caller` through `get_roles`, or with an `account` the ones recorded for it.
+    /// Standalone only.
+    pub fn roles(
+        &mut self,
+        caller: &AccountHash,
+        account: Option<AccountHash>,
+    ) -> Result<Vec<String>, DeployError> {
+        let args = locked::get_roles(self.locked_package_hash, account);
+        self.call_locked_entry_point(caller, entry_points::GET_ROLES, args)?;
+        Ok(self.query_account(caller, &[keys::ROLES]))
+    }
+
+    /// Approve the pending request of `account`, only callable by the admin group.
+    pub fn approve_access(&mut self, caller: &AccountHash, account: &AccountHash) -> Outcome {
+        let args = locked_with_share::approve_access(*account);
+        self.call_locked_entry_point(caller, entry_points::APPROVE_ACCESS, args)
+    }
+
+    /// Deny the pending request of `account`, only callable by the admin group.
+    pub fn deny_access(&mut self, caller: &AccountHash, account: &AccountHash) -> Outcome {
+        let args = locked_with_share::deny_access(*account);
+        self.call_locked_entry_point(caller, entry_points::DENY_ACCESS, args)
+    }
+
+    // Share contract administration, standalone only
+
+    /// Start handing the sharing contract over to `new_owner`, passing the package access URef along.
+    pub fn transfer_share_ownership(
+        &mut self,
+        caller: &AccountHash,
+        new_owner: &AccountHash,
+    ) -> Outcome {
+        let package_access = self.named_uref(caller, &keys::access_uref(keys::SHARE_CONTRACT));
+        self.call_share(
+            caller,
+            entry_points::TRANSFER_OWNERSHIP,
+            share::transfer_ownership(*new_owner, package_access),
+        )
+    }
+
+    /// Claim the URefs of a pending handover, then accept the ownership of the sharing contract.
+    pub fn accept_share_ownership(&mut self, caller: &AccountHash) -> Outcome {
+        let share_package_hash = self.share_package_hash();
+        self.claim(caller, Some(share_package_hash), None)?;
+        let package_access = self.named_uref(caller, keys::PACKAGE_ACCESS);
+        self.call_share(
+            caller,
+            entry_points::ACCEPT_OWNERSHIP,
+            share::accept_ownership(package_access),
+        )
+    }
+
+    /// Install a new version of the sharing contract with `args`, later calls go to the new version.
+    pub fn upgrade_share(&mut self, args: RuntimeArgs) -> Outcome {
+        let deployer = self.deployer;
+        self.run_wasm(&deployer, "uref-share.wasm", args)?;
+        let share_package_hash = self.share_package_hash();
+        self.share = Some((
+            self.query(&[&keys::wrapped(keys::SHARE_CONTRACT)]),
+            share_package_hash,
+        ));
+        Ok(())
+    }
+
+    /// Run the installer of the testing contract again with `args`. It reads all of them before
+    /// it fails to create the `admin` group a second time, so it only ever returns an error.
+    pub fn reinstall_locked(&mut self, args: RuntimeArgs) -> Outcome {
+        let deployer = self.deployer;
+        let wasm = match self.mode {
+            Mode::Standalone => "locked.wasm",
+            Mode::Integrated => "locked_with_share.wasm",
+        };
+        self.run_wasm(&deployer, wasm, args)
+    }
+
+    // Raw calls
+
+    /// Call `entry_point` of the testing contract with `args`.
+    pub fn call_locked_entry_point(
+        &mut self,
+        caller: &AccountHash,
+        entry_point: &str,
+        args: RuntimeArgs,
+    ) -> Outcome {
+        let locked_hash = self.locked_hash;
+        self.run_contract(caller, locked_hash, entry_point, args)
+    }
+
+    /// Call `entry_point` of the sharing contract with `args`.
+    pub fn call_share(
+        &mut self,
+        caller: &AccountHash,
+        entry_point: &str,
+        args: RuntimeArgs,
+    ) -> Outcome {
+        let share_hash = self.share_hash();
+        self.run_contract(caller, share_hash, entry_point, args)
+    }
+
+    /// Call `entry_point` of the receiver contract with `args`.
+    pub fn call_receiver(
+        &mut self,
+        caller: &AccountHash,
+        entry_point: &str,
+        args: RuntimeArgs,
+    ) -> Outcome {
+        let receiver_hash = self
+            .receiver_hash
+            .unwrap_or_else(|| panic!("receiver contract not deployed"));
+        self.run_contract(caller, receiver_hash, entry_point, args)
+    }
+
+    // Queries
+
+    /// Returns the access URef `account` holds under the `admin` named key.
+    pub fn held_uref(&self, account: &AccountHash) -> URef {
+        self.named_uref(account, DEFAULT_LABEL)
+    }
+
+    /// Returns the URef `account` holds under the named key `name`.
+    pub fn named_uref(&self, account: &AccountHash, name: &str) -> URef {
+        self.builder
+            .get_account(*account)
+            .unwrap_or_else(|| panic!("account not found"))
+            .named_keys()
+            .get(name)
+            .and_then(|key| key.into_uref())
+            .unwrap_or_else(|| panic!("account holds no URef under {}", name))
+    }
+
+    /// Reads the value at `path`, starting from the named keys of the deployer.
+    pub fn query<T: CLTyped + FromBytes>(&self, path: &[&str]) -> T {
+        self.query_account(&self.deployer, path)
+    }
+
+    /// Reads the value at `path`, starting from the named keys of `account`.
+    pub fn query_account<T: CLTyped + FromBytes>(&self, account: &AccountHash, path: &[&str]) -> T {
+        let path: Vec<String> = path.iter().map(|name| name.to_string()).collect();
+        let value = self
+            .builder
+            .query(None, Key::Account(*account), &path)
+            .unwrap_or_else(|_| panic!("{} not found", path.join("/")));
+        CLValue::try_from(value)
+            .unwrap_or_else(|_| panic!("{} is not a CLValue", path.join("/")))
+            .into_t()
+            .unwrap_or_else(|_| panic!("{} has wrong type", path.join("/")))
+    }
+
+    /// Balance of the purse the sharing contract holds deposit fees in.
+    pub fn fee_purse_balance(&self) -> U512 {
+        let fee_purse: [u8; 32] =
+            self.query(&[&keys::wrapped_fee_purse_address(keys::SHARE_CONTRACT)]);
+        self.builder
+            .get_purse_balance(URef::new(fee_purse, AccessRights::READ))
+    }
+
+    /// The main purse of `account`.
+    pub fn main_purse(&self, account: &AccountHash) -> URef {
+        self.builder
+            .get_account(*account)
+            .unwrap_or_else(|| panic!("account not found"))
+            .main_purse()
+    }
+
+    /// Runs `wasm` as session code of `caller`.
+    fn run_wasm(&mut self, caller: &AccountHash, wasm: &str, args: RuntimeArgs) -> Outcome {
+        let request = ExecuteRequestBuilder::standard(*caller, wasm, args);
+        self.exec(request)
+    }
+
+    /// Calls `entry_point` of the contract stored at `contract_hash` as `caller`.
+    fn run_contract(
+        &mut self,
+        caller: &AccountHash,
+        contract_hash: Hash,
+        entry_point: &str,
+        args: RuntimeArgs,
+    ) -> Outcome {
+        let request = ExecuteRequestBuilder::contract_call_by_hash(
+            *caller,
+            ContractHash::new(contract_hash),
+            entry_point,
+            args,
+        );
+        self.exec(request)
+    }
+
+    /// Executes the deploy at the current block time, committing it only if it succeeded.
+    fn exec(&mut self, request: ExecuteRequestBuilder) -> Outcome {
+        let request = request.with_block_time(self.block_time).build();
+        self.builder.exec(request);
+        if let Some(error) = self.builder.get_error() {
+            return Err(error.into());
+        }
+        self.builder.commit();
+        Ok(())
+    }
+
+    fn named_account(&self, name: &str) -> &(AccountHash, PublicKey) {
+        self.accounts
+            .get(name)
+            .unwrap_or_else(|| panic!("no account named {}", name))
+    }
+
+    fn share(&self) -> (Hash, ContractPackageHash) {
+        self.share
+            .unwrap_or_else(|| panic!("the sharing contract is only deployed standalone"))
+    }
+}
@@ -0,0 +1,284 @@
+// Gas benchmark of the install and claim paths.
+// Deploys both editions with a growing number of users, records the gas every deploy used
+// in a CSV report, then compares the report with the committed baseline.
+//
+//     cargo run --release --bin gas-bench -- [--report PATH] [--baseline PATH] [--tolerance PERCENT] [--save-baseline]
+use std::{collections::BTreeMap, env, fs, process};
+
+use casper_engine_test_support::internal::{
+    ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
+    DEFAULT_ACCOUNT_PUBLIC_KEY, DEFAULT_RUN_GENESIS_REQUEST,
+};
+use casper_types::{
+    runtime_args, ContractHash, ContractPackageHash, PublicKey, RuntimeArgs, SecretKey,
+};
+use uref_share_client::{args, entry_points, keys};
+
+/// Number of users the contracts are installed for, the claim path runs against a vault of that size.
+/// A package holds at most 100 group URefs, the installer keeps one of them.
+const USER_COUNTS: [usize; 3] = [10, 50, 90];
+
+const DEFAULT_REPORT: &str = "bench/gas.csv";
+const DEFAULT_BASELINE: &str = "bench/baseline.csv";
+/// Gas increase over the baseline, in percent, that is still not reported as a regression.
+const DEFAULT_TOLERANCE: f64 = 5.0;
+
+const CSV_HEADER: &str = "edition,path,users,gas";
+
+/// Where the URefs are stored between install and claim.
+#[derive(Clone, Copy)]
+enum Edition {
+    /// `locked.wasm`, storing its URefs in `uref-share.wasm`.
+    Standalone,
+    /// `locked_with_share.wasm`, storing its URefs itself.
+    Integrated,
+}
+
+impl Edition {
+    fn name(self) -> &'static str {
+        match self {
+            Edition::Standalone => "standalone",
+            Edition::Integrated => "integrated",
+        }
+    }
+}
+
+/// Gas used by one deploy, `None` if the deploy failed or could not run because an earlier one failed.
+type Measurements = BTreeMap<(String, String, usize), Option<u64>>;
+
+struct Options {
+    report: String,
+    baseline: String,
+    tolerance: f64,
+    save_baseline: bool,
+}
+
+fn main() {
+    let options = parse_options();
+
+    let mut measurements = Measurements::new();
+    for edition in [Edition::Standalone, Edition::Integrated].iter() {
+        for users in USER_COUNTS.iter() {
+            let (install, get_access) = bench(*edition, *users);
+            let key = |path: &str| (edition.name().to_string(), path.to_string(), *users);
+            measurements.insert(key("install"), install);
+            measurements.insert(key(entry_points::GET_ACCESS), get_access);
+        }
+    }
+
+    write_report(&options.report, &measurements);
+    println!("Wrote {}", options.report);
+    if options.save_baseline {
+        write_report(&options.baseline, &measurements);
+        println!("Saved the baseline to {}", options.baseline);
+        return;
+    }
+
+    let baseline = match fs::read_to_string(&options.baseline) {
+        Ok(contents) => read_report(&contents),
+        Err(error) => {
+            println!(
+                "No baseline at {} ({}), run with --save-baseline to record one",
+                options.baseline, error
+            );
+            process::exit(1);
+        }
+    };
+    for (edition, path, users) in measurements
+        .keys()
+        .filter(|key| !baseline.contains_key(*key))
+    {
+        println!(
+            "{} {} with {} users: not in the baseline yet, run with --save-baseline to record it",
+            edition, path, users
+        );
+    }
+    let regressions = compare(&baseline, &measurements, options.tolerance);
+    for regression in regressions.iter() {
+        println!("{}", regression);
+    }
+    if !regressions.is_empty() {
+        process::exit(1);
+    }
+    println!("No regressions against {}", options.baseline);
+}
+
+/// Installs `edition` for `users` users, the default account among them, and has it claim its URef.
+/// Returns the gas used by the install and by `get_access`.
+fn bench(edition: Edition, users: usize) -> (Option<u64>, Option<u64>) {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&*DEFAULT_RUN_GENESIS_REQUEST).commit();
+
+    let users: Vec<PublicKey> = std::iter::once(DEFAULT_ACCOUNT_PUBLIC_KEY.clone())
+        .chain((1..users).map(user_key))
+        .collect();
+
+    let (session, mut install_args, contract_name) = match edition {
+        Edition::Standalone => {
+            let share_install = ExecuteRequestBuilder::standard(
+                *DEFAULT_ACCOUNT_ADDR,
+                "uref-share.wasm",
+                runtime_args! {},
+            )
+            .build();
+            builder.exec(share_install).expect_success().commit();
+            let share_hash = package_hash(&builder, &keys::package_hash(keys::SHARE_CONTRACT));
+            (
+                "locked.wasm",
+                runtime_args! {args::SHARE_HASH => share_hash},
+                keys::LOCKED_CONTRACT,
+            )
+        }
+        Edition::Integrated => (
+            "locked_with_share.wasm",
+            RuntimeArgs::new(),
+            keys::LOCKED_WITH_SHARE_CONTRACT,
+        ),
+    };
+    install_args.insert(args::USERS, users).unwrap();
+
+    let install =
+        ExecuteRequestBuilder::standard(*DEFAULT_ACCOUNT_ADDR, session, install_args).build();
+    builder.exec(install).commit();
+    let install_gas = gas_used(&builder);
+    if install_gas.is_none() {
+        return (None, None);
+    }
+
+    let locked_hash = ContractHash::new(named_hash(&builder, contract_name));
+    let claim_args = runtime_args! {
+        args::THIS_CONTRACT => package_hash(&builder, &keys::package_hash(contract_name))
+    };
+    let get_access = ExecuteRequestBuilder::contract_call_by_hash(
+        *DEFAULT_ACCOUNT_ADDR,
+        locked_hash,
+        entry_points::GET_ACCESS,
+        claim_args,
+    )
+    .build();
+    builder.exec(get_access).commit();
+    (install_gas, gas_used(&builder))
+}
+
+/// Gas used by the last deploy, `None` if it failed.
+fn gas_used(builder: &InMemoryWasmTestBuilder) -> Option<u64> {
+    if builder.is_error() {
+        None
+    } else {
+        Some(builder.last_exec_gas_cost().value().as_u64())
+    }
+}
+
+/// The hash stored under `name` in the named keys of the default account.
+fn named_hash(builder: &InMemoryWasmTestBuilder, name: &str) -> [u8; 32] {
+    builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("default account not found")
+        .named_keys()
+        .get(name)
+        .and_then(|key| key.into_hash())
+        .unwrap_or_else(|| panic!("no hash named {}", name))
+}
+
+fn package_hash(builder: &InMemoryWasmTestBuilder, name: &str) -> ContractPackageHash {
+    ContractPackageHash::new(named_hash(builder, name))
+}
+
+/// Public key of the `index`th generated user.
+fn user_key(index: usize) -> PublicKey {
+    let mut seed = [0u8; 32];
+    seed[..8].copy_from_slice(&(index as u64).to_le_bytes());
+    SecretKey::ed25519_from_bytes(seed).unwrap().into()
+}
+
+/// Lists every measurement above the baseline by more than `tolerance` percent
+/// and every deploy that succeeded in the baseline but fails now.
+fn compare(baseline: &Measurements, measurements: &Measurements, tolerance: f64) -> Vec<String> {
+    let mut regressions = Vec::new();
+    for (key, gas) in measurements.iter() {
+        let (edition, path, users) = key;
+        match (baseline.get(key), gas) {
+            (Some(Some(_)), None) => regressions.push(format!(
+                "{} {} with {} users: failed, the baseline succeeded",
+                edition, path, users
+            )),
+            (Some(Some(expected)), Some(gas)) => {
+                let limit = *expected as f64 * (1.0 + tolerance / 100.0);
+                if *gas as f64 > limit {
+                    regressions.push(format!(
+                        "{} {} with {} users: {} gas, baseline {} (+{:.1}%)",
+                        edition,
+                        path,
+                        users,
+                        gas,
+                        expected,
+                        (*gas as f64 / *expected as f64 - 1.0) * 100.0
+                    ));
+                }
+            }
+            _ => {}
+        }
+    }
+    regressions
+}
+
+fn write_report(path: &str, measurements: &Measurements) {
+    let mut contents = format!("{}\n", CSV_HEADER);
+    for ((edition, path, users), gas) in measurements.iter() {
+        let gas = gas.map_or_else(|| "failed".to_string(), |gas| gas.to_string());
+        contents.push_str(&format!("{},{},{},{}\n", edition, path, users, gas));
+    }
+    if let Some(parent) = std::path::Path::new(path).parent() {
+        fs::create_dir_all(parent).unwrap_or_else(|error| panic!("{}: {}", path, error));
+    }
+    fs::write(path, contents).unwrap_or_else(|error| panic!("{}: {}", path, error));
+}
+
+fn read_report(contents: &str) -> Measurements {
+    contents
+        .lines()
+        .skip_while(|line| *line == CSV_HEADER)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let fields: Vec<&str> = line.split(',').collect();
+            if fields.len() != 4 {
+                panic!("malformed report line: {}", line);
+            }
+            let users = fields[2]
+                .parse()
+                .unwrap_or_else(|_| panic!("malformed user count: {}", line));
+            (
+                (fields[0].to_string(), fields[1].to_string(), users),
+                fields[3].parse().ok(),
+            )
+        })
+        .collect()
+}
+
+fn parse_options() -> Options {
+    let mut options = Options {
+        report: DEFAULT_REPORT.to_string(),
+        baseline: DEFAULT_BASELINE.to_string(),
+        tolerance: DEFAULT_TOLERANCE,
+        save_baseline: false,
+    };
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .unwrap_or_else(|| panic!("{} needs a value", arg))
+        };
+        match arg.as_str() {
+            "--report" => options.report = value(),
+            "--baseline" => options.baseline = value(),
+            "--tolerance" => {
+                options.tolerance = value()
+                    .parse()
+                    .unwrap_or_else(|_| panic!("--tolerance needs a number"))
+            }
+            "--save-baseline" => options.save_baseline = true,
+            _ => panic!("unknown argument {}", arg),
+        }
+    }
+    options
+}
@@ -1,118 +1,2027 @@
-mod integrated;
-mod standalone;
+#[cfg(test)]
+mod argument_checks;
+#[cfg(test)]
+mod client_args;
+mod fixture;
+#[cfg(test)]
+mod scenarios;
+#[cfg(test)]
+mod schema_check;
 
 #[cfg(test)]
 mod tests {
     // Standalone version tests
-    use super::standalone::ShareContract;
+    use super::fixture::{account_key, DeployError, Fixture, FixtureBuilder, Outcome, Role};
+    use casper_types::{
+        account::AccountHash, runtime_args, ContractPackageHash, Key, RuntimeArgs, U512,
+    };
 
     #[test]
-    #[should_panic(expected = "ApiError::User(777)")]
     fn deployer_calls_function() {
         // Deploy contracts.
-        let mut contract = ShareContract::deploy();
+        let mut contract = FixtureBuilder::standalone().build();
         // Admin calls contract, has access rights to it, and so can
-        contract.call_locked(&contract.admin.clone());
+        assert_eq!(contract.call_protected(&contract.account("admin")), Ok(()));
     }
 
     #[test]
-    #[should_panic(expected = "InvalidContext")]
     fn unauth_calls_function() {
         // Deploy contracts.
-        let mut contract = ShareContract::deploy();
+        let mut contract = FixtureBuilder::standalone().build();
 
         // Call restricted function with user who does not hava access to it,
         // and so the call reverts with InvalidContext error.
-        contract.call_locked(&contract.user.clone());
+        assert_eq!(
+            contract.call_protected(&contract.account("user")),
+            Err(DeployError::InvalidContext)
+        );
     }
 
     #[test]
-    #[should_panic(expected = "ApiError::User(777)")]
     fn getting_access() {
         // Deploy contracts.
-        let mut contract = ShareContract::deploy();
+        let mut contract = FixtureBuilder::standalone().build();
 
         // User retrieves access rights to the function.
-        contract.get_access(&contract.user.clone());
+        contract.claim_access(&contract.account("user")).unwrap();
 
         // User now has access rights to call the access restricted function.
-        contract.call_locked(&contract.user.clone());
-
-        // Test does not reach this unreachable,
-        // since we managed to call the contract that reverts with User(777) error.
-        unreachable!();
+        assert_eq!(contract.call_protected(&contract.account("user")), Ok(()));
     }
 
     #[test]
-    #[should_panic(expected = "User(1)")]
     fn unable_to_gain_access() {
         // Deploy contracts.
-        let mut contract = ShareContract::deploy();
-
-        // User tries to retrieve access rights to the function.
-        contract.get_access(&contract.unauth.clone());
+        let mut contract = FixtureBuilder::standalone().build();
 
         // This user was not designated on deployment to recieve access rights,
         // so they recieve User(1) error, which means they would have gotten the "default" URef value.
-        contract.call_locked(&contract.unauth.clone());
-        unreachable!();
+        assert_eq!(
+            contract.claim_access(&contract.account("unauth")),
+            Err(DeployError::User(1))
+        );
+
+        // Without the URef the call is turned away by the engine.
+        assert_eq!(
+            contract.call_protected(&contract.account("unauth")),
+            Err(DeployError::InvalidContext)
+        );
+    }
+
+    #[test]
+    fn failed_call_leaves_state_intact() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::standalone().build();
+
+        // User calls before claiming and is turned away.
+        assert_eq!(
+            contract.call_protected(&contract.account("user")),
+            Err(DeployError::InvalidContext)
+        );
+
+        // The failed deploy is not committed, so the user can still claim and call.
+        contract.claim_access(&contract.account("user")).unwrap();
+        assert_eq!(contract.call_protected(&contract.account("user")), Ok(()));
+    }
+
+    #[test]
+    fn delegated_access() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::standalone().build();
+
+        // User retrieves access rights, then passes them on to the unauthorized account.
+        contract.claim_access(&contract.account("user")).unwrap();
+        contract
+            .delegate(&contract.account("user"), &contract.public_key("unauth"))
+            .unwrap();
+
+        // The delegate picks the URef up the same way as a direct recipient.
+        contract.claim_access(&contract.account("unauth")).unwrap();
+        assert_eq!(contract.call_protected(&contract.account("unauth")), Ok(()));
+    }
+
+    #[test]
+    fn delegation_depth_exceeded() {
+        // Deploy contracts, delegation depth defaults to a single hop.
+        let mut contract = FixtureBuilder::standalone().build();
+
+        contract.claim_access(&contract.account("user")).unwrap();
+        contract
+            .delegate(&contract.account("user"), &contract.public_key("unauth"))
+            .unwrap();
+        contract.claim_access(&contract.account("unauth")).unwrap();
+
+        // A second hop is past the limit.
+        assert_eq!(
+            contract.delegate(&contract.account("unauth"), &contract.public_key("admin")),
+            Err(DeployError::User(101))
+        );
+    }
+
+    #[test]
+    fn revocation_cascades() {
+        // Deploy contracts allowing chains of two delegations.
+        let mut contract = FixtureBuilder::standalone()
+            .share_args(runtime_args! {"max_delegation_depth" => 2u8})
+            .build();
+
+        // User -> unauth -> admin.
+        contract.claim_access(&contract.account("user")).unwrap();
+        contract
+            .delegate(&contract.account("user"), &contract.public_key("unauth"))
+            .unwrap();
+        contract.claim_access(&contract.account("unauth")).unwrap();
+        contract
+            .delegate(&contract.account("unauth"), &contract.public_key("admin"))
+            .unwrap();
+
+        // Revoking the first hop also withdraws the deposit made further down the chain.
+        contract
+            .revoke(&contract.account("user"), &contract.public_key("unauth"))
+            .unwrap();
+        assert_eq!(
+            contract.claim_access(&contract.account("admin")),
+            Err(DeployError::User(1))
+        );
+    }
+
+    #[test]
+    fn revoked_after_claim() {
+        // Deploy contracts allowing chains of two delegations.
+        let mut contract = FixtureBuilder::standalone()
+            .share_args(runtime_args! {"max_delegation_depth" => 2u8})
+            .build();
+
+        // The unauthorized account already installed the delegated URef when it is revoked.
+        let unauth = contract.account("unauth");
+        contract.claim_access(&contract.account("user")).unwrap();
+        contract
+            .delegate(&contract.account("user"), &contract.public_key("unauth"))
+            .unwrap();
+        contract.claim_access(&unauth).unwrap();
+        assert_eq!(contract.call_protected(&unauth), Ok(()));
+        contract
+            .revoke(&contract.account("user"), &contract.public_key("unauth"))
+            .unwrap();
+
+        // The sharing contract no longer counts the URef as held, so it can not be passed on.
+        assert_eq!(
+            contract.delegate(&unauth, &contract.public_key("admin")),
+            Err(DeployError::User(100))
+        );
+        // The locked contract issued the delegate a URef of its own and took it back,
+        // the URef of the delegator keeps working.
+        assert_eq!(
+            contract.call_protected(&unauth),
+            Err(DeployError::InvalidContext)
+        );
+        assert_eq!(contract.call_protected(&contract.account("user")), Ok(()));
+    }
+
+    #[test]
+    fn redelegation_after_revocation() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::standalone().build();
+
+        // The delegation can be made again once revoked, and the delegate gets a fresh URef.
+        let unauth = contract.account("unauth");
+        contract.claim_access(&contract.account("user")).unwrap();
+        contract
+            .delegate(&contract.account("user"), &contract.public_key("unauth"))
+            .unwrap();
+        assert_eq!(
+            contract.delegate(&contract.account("user"), &contract.public_key("unauth")),
+            Err(DeployError::User(102))
+        );
+        contract
+            .revoke(&contract.account("user"), &contract.public_key("unauth"))
+            .unwrap();
+        contract
+            .delegate(&contract.account("user"), &contract.public_key("unauth"))
+            .unwrap();
+        contract.claim_access(&unauth).unwrap();
+        assert_eq!(contract.call_protected(&unauth), Ok(()));
+    }
+
+    #[test]
+    fn untagged_entry_can_not_be_delegated() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::standalone().build();
+
+        // The deployer shares its URef directly, without a source package to issue one for a delegate.
+        let unauth = contract.account("unauth");
+        let deployer = contract.deployer();
+        contract
+            .deposit(
+                &deployer,
+                "locked_deployer_admin_access",
+                Key::Account(unauth),
+            )
+            .unwrap();
+        contract.claim_access(&unauth).unwrap();
+        assert_eq!(
+            contract.delegate(&unauth, &contract.public_key("user")),
+            Err(DeployError::User(120))
+        );
+    }
+
+    #[test]
+    fn release_requires_approval() {
+        // Deploy contracts, with the admin as the only approver.
+        let mut contract = FixtureBuilder::standalone()
+            .share_args(runtime_args! {
+                "approvers" => vec![account_key(1)],
+                "approval_threshold" => 1u8
+            })
+            .build();
+
+        // Nobody approved the deposit yet, so it can not be retrieved.
+        assert_eq!(
+            contract.claim_access(&contract.account("user")),
+            Err(DeployError::User(106))
+        );
+    }
+
+    #[test]
+    fn release_after_approval() {
+        // Deploy contracts, with the admin as the only approver.
+        let mut contract = FixtureBuilder::standalone()
+            .share_args(runtime_args! {
+                "approvers" => vec![account_key(1)],
+                "approval_threshold" => 1u8
+            })
+            .build();
+
+        contract
+            .approve_release(&contract.account("admin"), &contract.public_key("user"))
+            .unwrap();
+        contract.claim_access(&contract.account("user")).unwrap();
+        assert_eq!(contract.call_protected(&contract.account("user")), Ok(()));
+    }
+
+    #[test]
+    fn approval_by_non_approver() {
+        // Deploy contracts, with the admin as the only approver.
+        let mut contract = FixtureBuilder::standalone()
+            .share_args(runtime_args! {
+                "approvers" => vec![account_key(1)],
+                "approval_threshold" => 1u8
+            })
+            .build();
+
+        // Recipients can not approve their own deposit.
+        assert_eq!(
+            contract.approve_release(&contract.account("user"), &contract.public_key("user")),
+            Err(DeployError::User(105))
+        );
+    }
+
+    #[test]
+    fn approval_does_not_cover_later_entries() {
+        // Deploy contracts, with the admin as the only approver.
+        let mut contract = FixtureBuilder::standalone()
+            .share_args(runtime_args! {
+                "approvers" => vec![account_key(1)],
+                "approval_threshold" => 1u8
+            })
+            .build();
+        contract
+            .approve_release(&contract.account("admin"), &contract.public_key("user"))
+            .unwrap();
+
+        // An entry deposited after the approval waits for approvals of its own,
+        // the approved one stays released.
+        let uref = contract.named_uref(&contract.account("admin"), "locked_deployer_admin_access");
+        contract
+            .call_share(
+                &contract.account("admin"),
+                "store_uref",
+                runtime_args! {
+                    "uref" => uref,
+                    "recipient" => Key::Account(contract.account("user")),
+                    "label" => "extra".to_string()
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            contract.claim(&contract.account("user"), None, Some("extra")),
+            Err(DeployError::User(106))
+        );
+        contract.claim_access(&contract.account("user")).unwrap();
+        assert_eq!(contract.call_protected(&contract.account("user")), Ok(()));
+    }
+
+    /// Deploys the contracts and upgrades the sharing contract to take one approval from
+    /// the holders of an `admin` URef of the testing contract.
+    fn deploy_with_approver_package() -> Fixture {
+        let mut contract = FixtureBuilder::standalone().build();
+        let locked_package_hash = contract.locked_package_hash;
+        contract
+            .upgrade_share(runtime_args! {
+                "approval_threshold" => 1u8,
+                "approver_package" => locked_package_hash
+            })
+            .unwrap();
+        contract
+    }
+
+    #[test]
+    fn release_approved_by_group() {
+        let mut contract = deploy_with_approver_package();
+        let user = contract.account("user");
+        assert_eq!(contract.claim_access(&user), Err(DeployError::User(106)));
+
+        // The admin is no approver unless it passes its URef.
+        assert_eq!(
+            contract.approve_release(&contract.account("admin"), &contract.public_key("user")),
+            Err(DeployError::User(105))
+        );
+        let uref = contract.named_uref(&contract.account("admin"), "locked_deployer_admin_access");
+        contract
+            .call_share(
+                &contract.account("admin"),
+                "approve_release",
+                runtime_args! {"recipient" => Key::Account(user), "uref" => uref},
+            )
+            .unwrap();
+        contract.claim_access(&user).unwrap();
+        assert_eq!(contract.call_protected(&user), Ok(()));
+    }
+
+    #[test]
+    fn approval_with_uref_outside_group() {
+        let mut contract = deploy_with_approver_package();
+
+        let uref = contract.main_purse(&contract.account("unauth"));
+        assert_eq!(
+            contract.call_share(
+                &contract.account("unauth"),
+                "approve_release",
+                runtime_args! {"recipient" => Key::Account(contract.account("user")), "uref" => uref},
+            ),
+            Err(DeployError::InvalidContext)
+        );
+    }
+
+    #[test]
+    fn claim_session() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::standalone().build();
+
+        // User claims everything pending through the standalone session code.
+        contract
+            .claim(&contract.account("user"), None, None)
+            .unwrap();
+        assert_eq!(contract.call_protected(&contract.account("user")), Ok(()));
+    }
+
+    #[test]
+    fn claim_session_filtered() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::standalone().build();
+
+        let locked_package_hash = contract.locked_package_hash;
+        contract
+            .claim(
+                &contract.account("user"),
+                Some(locked_package_hash),
+                Some("admin"),
+            )
+            .unwrap();
+        assert_eq!(contract.call_protected(&contract.account("user")), Ok(()));
+    }
+
+    #[test]
+    fn claim_session_other_source() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::standalone().build();
+
+        // Nothing was deposited on behalf of the sharing contract itself.
+        let share_package_hash = contract.share_package_hash();
+        assert_eq!(
+            contract.claim(&contract.account("user"), Some(share_package_hash), None),
+            Err(DeployError::User(1))
+        );
+    }
+
+    #[test]
+    fn claim_session_twice() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::standalone().build();
+
+        // Claimed entries are no longer pending.
+        contract
+            .claim(&contract.account("user"), None, None)
+            .unwrap();
+        assert_eq!(
+            contract.claim(&contract.account("user"), None, None),
+            Err(DeployError::User(1))
+        );
+    }
+
+    #[test]
+    fn deposit_from_package() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::standalone().build();
+
+        // The user passes its own admin URef on, the testing contract confirms it as one of its own.
+        contract.claim_access(&contract.account("user")).unwrap();
+        let uref = contract.held_uref(&contract.account("user"));
+        let locked_package_hash = contract.locked_package_hash;
+        contract
+            .deposit_from_package(
+                &contract.account("user"),
+                uref,
+                Key::Account(contract.account("unauth")),
+                locked_package_hash,
+            )
+            .unwrap();
+        contract.claim_access(&contract.account("unauth")).unwrap();
+        assert_eq!(contract.call_protected(&contract.account("unauth")), Ok(()));
+    }
+
+    #[test]
+    fn deposit_from_spoofed_package() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::standalone().build();
+
+        // Any URef but an admin one is turned away by the group check of `confirm_deposit`.
+        let uref = contract.main_purse(&contract.account("unauth"));
+        let locked_package_hash = contract.locked_package_hash;
+        assert_eq!(
+            contract.deposit_from_package(
+                &contract.account("unauth"),
+                uref,
+                Key::Account(contract.account("user")),
+                locked_package_hash,
+            ),
+            Err(DeployError::InvalidContext)
+        );
+    }
+
+    #[test]
+    fn deposit_from_share_package() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::standalone().build();
+
+        // Entries of the sharing contract itself are ownership handovers, made by the contract only.
+        let uref = contract.main_purse(&contract.account("unauth"));
+        let share_package_hash = contract.share_package_hash();
+        assert_eq!(
+            contract.deposit_from_package(
+                &contract.account("unauth"),
+                uref,
+                Key::Account(contract.account("user")),
+                share_package_hash,
+            ),
+            Err(DeployError::User(119))
+        );
+    }
+
+    #[test]
+    fn contract_recipient() {
+        // Deploy contracts, the receiver contract gets its URef pushed during install.
+        let mut contract = FixtureBuilder::standalone().with_receiver().build();
+
+        // The installer of the receiver makes it call the restricted function,
+        // the access rights come from the receivers named keys.
+        assert_eq!(
+            contract.receiver_call_locked(&contract.account("admin")),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn contract_recipient_called_by_non_operator() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::standalone().with_receiver().build();
+
+        // Other accounts lack the operator URef.
+        assert_eq!(
+            contract.receiver_call_locked(&contract.account("unauth")),
+            Err(DeployError::InvalidContext)
+        );
+    }
+
+    #[test]
+    fn contract_recipient_pushed_by_account() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::standalone().with_receiver().build();
+
+        // Handing the receiver a URef directly is turned away, the sharing contract is not pushing it.
+        let uref = contract.main_purse(&contract.account("unauth"));
+        assert_eq!(
+            contract.call_receiver(
+                &contract.account("unauth"),
+                "receive_uref",
+                runtime_args! {
+                    "uref" => uref,
+                    "label" => "admin".to_string(),
+                    "source_package" => None::<ContractPackageHash>
+                },
+            ),
+            Err(DeployError::User(300))
+        );
+    }
+
+    #[test]
+    fn contract_recipient_requires_approval() {
+        // Deploy contracts, with the admin as the only approver.
+        let mut contract = FixtureBuilder::standalone()
+            .share_args(runtime_args! {
+                "approvers" => vec![account_key(1)],
+                "approval_threshold" => 1u8
+            })
+            .with_receiver()
+            .build();
+
+        // Nothing is pushed before the approval.
+        assert_eq!(
+            contract.receiver_call_locked(&contract.account("admin")),
+            Err(DeployError::InvalidContext)
+        );
+
+        // Approving pushes the pending URef.
+        let receiver = Key::Hash(contract.receiver_package_hash.unwrap().value());
+        contract
+            .call_share(
+                &contract.account("admin"),
+                "approve_release",
+                runtime_args! {"recipient" => receiver},
+            )
+            .unwrap();
+        assert_eq!(
+            contract.receiver_call_locked(&contract.account("admin")),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn contract_recipient_quota_exceeded() {
+        // The locked contract deposits three admin URefs for accounts, the one for the receiver is too many.
+        let install = FixtureBuilder::standalone()
+            .share_args(runtime_args! {"max_total_entries" => 3u32})
+            .with_receiver()
+            .try_build();
+        assert_eq!(install.err(), Some(DeployError::User(114)));
+    }
+
+    #[test]
+    fn secp256k1_recipient() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::standalone().build();
+
+        // The secp256k1 key normalizes to the same account hash the account calls with.
+        contract
+            .claim_access(&contract.account("secp_user"))
+            .unwrap();
+        assert_eq!(
+            contract.call_protected(&contract.account("secp_user")),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn account_hash_recipient() {
+        // Deploy contracts, one recipient is only known by its account hash.
+        let mut contract = FixtureBuilder::standalone().build();
+
+        contract
+            .claim_access(&contract.account("hash_only_user"))
+            .unwrap();
+        assert_eq!(
+            contract.call_protected(&contract.account("hash_only_user")),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn key_recipient() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::standalone().build();
+
+        // The deployer shares its own URef, naming the recipient with a generic `Key`.
+        let unauth = contract.account("unauth");
+        contract
+            .deposit(
+                &contract.account("admin"),
+                "locked_deployer_admin_access",
+                Key::Account(unauth),
+            )
+            .unwrap();
+        contract.claim_access(&unauth).unwrap();
+        assert_eq!(contract.call_protected(&unauth), Ok(()));
+    }
+
+    #[test]
+    fn untagged_deposit_does_not_replace_access() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::standalone().build();
+
+        // Anyone can deposit an untagged URef for the user, here the main purse of another account.
+        let unauth = contract.account("unauth");
+        let user = contract.account("user");
+        let junk = contract.main_purse(&unauth);
+        contract
+            .call_share(
+                &unauth,
+                "store_uref",
+                runtime_args! {"uref" => junk, "account_hash" => user},
+            )
+            .unwrap();
+        // The entry tagged with the testing contract is claimed, the untagged one is left pending.
+        contract.claim_access(&user).unwrap();
+        assert_eq!(contract.call_protected(&user), Ok(()));
+        // Nor is it claimed over the access URef later on.
+        assert_eq!(contract.claim_access(&user), Err(DeployError::User(1)));
+        assert_eq!(contract.call_protected(&user), Ok(()));
+    }
+
+    #[test]
+    fn unsupported_key_recipient() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::standalone().build();
+
+        let uref = contract.named_uref(&contract.account("admin"), "locked_deployer_admin_access");
+        assert_eq!(
+            contract.deposit(
+                &contract.account("admin"),
+                "locked_deployer_admin_access",
+                Key::URef(uref),
+            ),
+            Err(DeployError::User(108))
+        );
+    }
+
+    #[test]
+    fn paused_claims() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::standalone().build();
+
+        // The owner freezes the sharing contract, pending URefs can not be retrieved.
+        contract
+            .call_share(&contract.account("admin"), "pause", runtime_args! {})
+            .unwrap();
+        assert_eq!(
+            contract.claim_access(&contract.account("user")),
+            Err(DeployError::User(111))
+        );
+    }
+
+    #[test]
+    fn unpaused_claims() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::standalone().build();
+
+        contract
+            .call_share(&contract.account("admin"), "pause", runtime_args! {})
+            .unwrap();
+        contract
+            .call_share(&contract.account("admin"), "unpause", runtime_args! {})
+            .unwrap();
+        contract.claim_access(&contract.account("user")).unwrap();
+        assert_eq!(contract.call_protected(&contract.account("user")), Ok(()));
+    }
+
+    #[test]
+    fn depositor_quota_exceeded() {
+        // The locked contract deposits three admin URefs on install, one more than allowed.
+        let install = FixtureBuilder::standalone()
+            .share_args(runtime_args! {"max_pending_per_depositor" => 2u32})
+            .try_build();
+        assert_eq!(install.err(), Some(DeployError::User(112)));
+    }
+
+    #[test]
+    fn global_quota_exceeded() {
+        // The locked contract deposits three admin URefs on install, one more than allowed.
+        let install = FixtureBuilder::standalone()
+            .share_args(runtime_args! {"max_total_entries" => 2u32})
+            .try_build();
+        assert_eq!(install.err(), Some(DeployError::User(114)));
+    }
+
+    #[test]
+    fn recipient_quota_exceeded() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::standalone().build();
+
+        // The owner lowers the quotas, the user already has one unclaimed entry.
+        contract
+            .call_share(
+                &contract.account("admin"),
+                "set_quotas",
+                runtime_args! {
+                    "max_pending_per_depositor" => 0u32,
+                    "max_entries_per_recipient" => 1u32,
+                    "max_total_entries" => 0u32
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            contract.deposit(
+                &contract.account("admin"),
+                "locked_deployer_admin_access",
+                Key::Account(contract.account("user")),
+            ),
+            Err(DeployError::User(113))
+        );
+    }
+
+    #[test]
+    fn quota_released_on_claim() {
+        // Deploy contracts, the install deposits use up the admin's quota.
+        let mut contract = FixtureBuilder::standalone()
+            .share_args(runtime_args! {"max_pending_per_depositor" => 3u32})
+            .build();
+
+        // Once the user claimed its entry, the admin can deposit again.
+        contract.claim_access(&contract.account("user")).unwrap();
+        contract
+            .deposit(
+                &contract.account("admin"),
+                "locked_deployer_admin_access",
+                Key::Account(contract.account("unauth")),
+            )
+            .unwrap();
+        contract.claim_access(&contract.account("unauth")).unwrap();
+        assert_eq!(contract.call_protected(&contract.account("unauth")), Ok(()));
+    }
+
+    #[test]
+    fn contractor_access_within_lease() {
+        // Deploy contracts, the contractor gets admin access for an hour.
+        let mut contract = FixtureBuilder::standalone()
+            .role("contractor", Role::Contractor)
+            .locked_args(runtime_args! {
+                "lease_duration" => 3_600_000u64
+            })
+            .build();
+
+        // Sweeping leaves leases that did not end yet alone.
+        contract
+            .call_locked_entry_point(
+                &contract.account("admin"),
+                "sweep_expired",
+                runtime_args! {},
+            )
+            .unwrap();
+        contract
+            .claim_access(&contract.account("contractor"))
+            .unwrap();
+        assert_eq!(
+            contract.call_protected(&contract.account("contractor")),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn contractor_access_after_lease() {
+        let mut contract = FixtureBuilder::standalone()
+            .role("contractor", Role::Contractor)
+            .locked_args(runtime_args! {
+                "lease_duration" => 3_600_000u64
+            })
+            .build();
+        contract
+            .claim_access(&contract.account("contractor"))
+            .unwrap();
+
+        // The URef is still in the group, but the lease ended without a sweep.
+        contract.set_block_time(3_600_000);
+        assert_eq!(
+            contract.call_protected(&contract.account("contractor")),
+            Err(DeployError::User(12))
+        );
+        assert_eq!(
+            contract.call_locked_entry_point(
+                &contract.account("contractor"),
+                "metered_access",
+                runtime_args! {},
+            ),
+            Err(DeployError::User(12))
+        );
+        // Accounts without a lease are not affected.
+        contract.claim_access(&contract.account("user")).unwrap();
+        assert_eq!(contract.call_protected(&contract.account("user")), Ok(()));
+    }
+
+    #[test]
+    fn renew_not_renewable() {
+        let mut contract = FixtureBuilder::standalone()
+            .role("contractor", Role::Contractor)
+            .locked_args(runtime_args! {
+                "lease_duration" => 3_600_000u64
+            })
+            .build();
+
+        assert_eq!(
+            contract.call_locked_entry_point(
+                &contract.account("contractor"),
+                "renew",
+                runtime_args! {},
+            ),
+            Err(DeployError::User(2))
+        );
+    }
+
+    #[test]
+    fn renew_without_lease() {
+        let mut contract = FixtureBuilder::standalone()
+            .role("contractor", Role::Contractor)
+            .locked_args(runtime_args! {
+                "lease_duration" => 3_600_000u64,
+                "renewable" => true
+            })
+            .build();
+
+        // Plain users hold their access without a lease.
+        assert_eq!(
+            contract.call_locked_entry_point(&contract.account("user"), "renew", runtime_args! {}),
+            Err(DeployError::User(3))
+        );
+    }
+
+    #[test]
+    fn renew_lease() {
+        let mut contract = FixtureBuilder::standalone()
+            .role("contractor", Role::Contractor)
+            .locked_args(runtime_args! {
+                "lease_duration" => 3_600_000u64,
+                "renewable" => true
+            })
+            .build();
+
+        contract
+            .claim_access(&contract.account("contractor"))
+            .unwrap();
+
+        // Renewing shortly before the end counts the hour from then on.
+        contract.set_block_time(3_000_000);
+        contract
+            .call_locked_entry_point(&contract.account("contractor"), "renew", runtime_args! {})
+            .unwrap();
+        contract.set_block_time(6_599_999);
+        assert_eq!(
+            contract.call_protected(&contract.account("contractor")),
+            Ok(())
+        );
+        contract.set_block_time(6_600_000);
+        assert_eq!(
+            contract.call_protected(&contract.account("contractor")),
+            Err(DeployError::User(12))
+        );
+        assert_eq!(
+            contract.call_locked_entry_point(
+                &contract.account("contractor"),
+                "renew",
+                runtime_args! {}
+            ),
+            Err(DeployError::User(4))
+        );
+    }
+
+    #[test]
+    fn check_access_with_access() {
+        let mut contract = FixtureBuilder::standalone().build();
+
+        // The roles end up in the callers account, where the client reads them.
+        contract.claim_access(&contract.account("user")).unwrap();
+        assert_eq!(
+            contract.roles(&contract.account("user"), None),
+            Ok(vec!["admin".to_string()])
+        );
+        assert_eq!(
+            contract.roles(&contract.account("admin"), None),
+            Ok(vec!["admin".to_string(), "deployer".to_string()])
+        );
+    }
+
+    #[test]
+    fn check_access_without_access() {
+        let mut contract = FixtureBuilder::standalone().build();
+
+        assert_eq!(
+            contract.roles(&contract.account("unauth"), None),
+            Err(DeployError::InvalidContext)
+        );
+    }
+
+    #[test]
+    fn roles_of_without_access() {
+        let mut contract = FixtureBuilder::standalone().build();
+
+        // Anyone can look up the roles of any account, including ones without access.
+        let unauth = contract.account("unauth");
+        assert_eq!(contract.roles(&unauth, Some(unauth)), Ok(vec![]));
+        // Holders are in the registry before they claimed their URef.
+        let user = contract.account("user");
+        assert_eq!(
+            contract.roles(&unauth, Some(user)),
+            Ok(vec!["admin".to_string()])
+        );
+    }
+
+    #[test]
+    fn allowance_used_up() {
+        // Deploy contracts, admins may call protected entry points once.
+        let mut contract = FixtureBuilder::standalone()
+            .locked_args(runtime_args! {
+                "allowances" => vec![("admin".to_string(), 1u32)]
+            })
+            .build();
+
+        contract.claim_access(&contract.account("user")).unwrap();
+        contract
+            .call_locked_entry_point(
+                &contract.account("user"),
+                "metered_access",
+                runtime_args! {},
+            )
+            .unwrap();
+        assert_eq!(
+            contract.call_protected(&contract.account("user")),
+            Err(DeployError::User(5))
+        );
+    }
+
+    #[test]
+    fn allowance_used_up_on_group_access_only() {
+        let mut contract = FixtureBuilder::standalone()
+            .locked_args(runtime_args! {
+                "allowances" => vec![("admin".to_string(), 2u32)]
+            })
+            .build();
+
+        contract.claim_access(&contract.account("user")).unwrap();
+        contract.call_protected(&contract.account("user")).unwrap();
+        contract.call_protected(&contract.account("user")).unwrap();
+        assert_eq!(
+            contract.call_protected(&contract.account("user")),
+            Err(DeployError::User(5))
+        );
+        // The allowance is per caller.
+        assert_eq!(contract.call_protected(&contract.account("admin")), Ok(()));
+    }
+
+    #[test]
+    fn allowance_topped_up() {
+        let mut contract = FixtureBuilder::standalone()
+            .locked_args(runtime_args! {
+                "allowances" => vec![("admin".to_string(), 1u32)]
+            })
+            .build();
+
+        contract.claim_access(&contract.account("user")).unwrap();
+        contract
+            .call_locked_entry_point(
+                &contract.account("user"),
+                "metered_access",
+                runtime_args! {},
+            )
+            .unwrap();
+        contract.call_locked_entry_point(
+            &contract.account("admin"),
+            "top_up",
+            runtime_args! {"account" => contract.account("user"), "role" => "admin".to_string(), "calls" => 1u32},
+        ).unwrap();
+        assert_eq!(contract.call_protected(&contract.account("user")), Ok(()));
+    }
+
+    #[test]
+    fn allowance_reset() {
+        let mut contract = FixtureBuilder::standalone()
+            .locked_args(runtime_args! {
+                "allowances" => vec![("admin".to_string(), 1u32)]
+            })
+            .build();
+
+        contract.claim_access(&contract.account("user")).unwrap();
+        contract
+            .call_locked_entry_point(
+                &contract.account("user"),
+                "metered_access",
+                runtime_args! {},
+            )
+            .unwrap();
+        contract.call_locked_entry_point(
+            &contract.account("admin"),
+            "reset_usage",
+            runtime_args! {"account" => contract.account("user"), "role" => "admin".to_string()},
+        ).unwrap();
+        assert_eq!(contract.call_protected(&contract.account("user")), Ok(()));
+    }
+
+    #[test]
+    fn allowance_topped_up_without_access() {
+        let mut contract = FixtureBuilder::standalone()
+            .locked_args(runtime_args! {
+                "allowances" => vec![("admin".to_string(), 1u32)]
+            })
+            .build();
+
+        // Managing the allowances takes an `admin` URef.
+        assert_eq!(contract.call_locked_entry_point(
+            &contract.account("unauth"),
+            "top_up",
+            runtime_args! {"account" => contract.account("unauth"), "role" => "admin".to_string(), "calls" => 1u32},
+        ), Err(DeployError::InvalidContext));
+    }
+
+    /// Deploys the contracts with the admin URefs deposited in the `onboarding` bundle,
+    /// and has the admin add its own URef to the users bundle under `extra`.
+    fn deploy_with_bundle() -> Fixture {
+        let mut contract = FixtureBuilder::standalone()
+            .locked_args(runtime_args! {
+                "bundle" => "onboarding".to_string()
+            })
+            .build();
+        let uref = contract.named_uref(&contract.account("admin"), "locked_deployer_admin_access");
+        contract
+            .call_share(
+                &contract.account("admin"),
+                "store_uref",
+                runtime_args! {
+                    "uref" => uref,
+                    "recipient" => Key::Account(contract.account("user")),
+                    "label" => "extra".to_string(),
+                    "bundle" => "onboarding".to_string()
+                },
+            )
+            .unwrap();
+        contract
+    }
+
+    #[test]
+    fn bundle_access() {
+        let mut contract = deploy_with_bundle();
+
+        // One call installs the URefs of the whole bundle.
+        contract
+            .claim_bundle_access(&contract.account("user"), "onboarding")
+            .unwrap();
+        let admin_name = format!(
+            "{}-admin",
+            contract.locked_package_hash.to_formatted_string()
+        );
+        let admin = contract.named_uref(&contract.account("user"), &admin_name);
+        let extra = contract.named_uref(&contract.account("user"), "extra");
+        let deployer_access =
+            contract.named_uref(&contract.account("admin"), "locked_deployer_admin_access");
+        assert_eq!(extra.addr(), deployer_access.addr());
+        assert_ne!(admin.addr(), extra.addr());
+    }
+
+    #[test]
+    fn bundle_claimed_in_part() {
+        let mut contract = deploy_with_bundle();
+
+        // An entry claimed on its own leaves the bundle, the rest of it can still be claimed as a unit.
+        contract
+            .claim(&contract.account("user"), None, Some("extra"))
+            .unwrap();
+        contract
+            .claim_bundle_access(&contract.account("user"), "onboarding")
+            .unwrap();
+    }
+
+    #[test]
+    fn bundle_entry_reclaimed() {
+        let mut contract = deploy_with_bundle();
+
+        contract
+            .call_share(
+                &contract.account("admin"),
+                "reclaim_uref",
+                runtime_args! {
+                    "recipient" => Key::Account(contract.account("user")),
+                    "label" => "extra".to_string()
+                },
+            )
+            .unwrap();
+        contract
+            .claim_bundle_access(&contract.account("user"), "onboarding")
+            .unwrap();
+    }
+
+    #[test]
+    fn bundle_entry_replaced_by_other_depositor() {
+        let mut contract = deploy_with_bundle();
+
+        // The replacement does not join the bundle, and is left pending when the bundle is claimed.
+        let uref = contract.main_purse(&contract.account("unauth"));
+        contract
+            .call_share(
+                &contract.account("unauth"),
+                "store_uref",
+                runtime_args! {
+                    "uref" => uref,
+                    "recipient" => Key::Account(contract.account("user")),
+                    "label" => "extra".to_string()
+                },
+            )
+            .unwrap();
+        contract
+            .claim_bundle_access(&contract.account("user"), "onboarding")
+            .unwrap();
+        let user = contract
+            .builder
+            .get_account(contract.account("user"))
+            .unwrap();
+        assert!(!user.named_keys().contains_key("extra"));
+    }
+
+    #[test]
+    fn bundle_of_other_depositor() {
+        let mut contract = deploy_with_bundle();
+
+        // Only the depositor that created a bundle adds entries to it.
+        let uref = contract.main_purse(&contract.account("unauth"));
+        assert_eq!(
+            contract.call_share(
+                &contract.account("unauth"),
+                "store_uref",
+                runtime_args! {
+                    "uref" => uref,
+                    "recipient" => Key::Account(contract.account("user")),
+                    "label" => "intruder".to_string(),
+                    "bundle" => "onboarding".to_string()
+                },
+            ),
+            Err(DeployError::User(122))
+        );
+    }
+
+    #[test]
+    fn bundle_for_contract_recipient() {
+        let mut contract = FixtureBuilder::standalone().with_receiver().build();
+
+        let uref = contract.named_uref(&contract.account("admin"), "locked_deployer_admin_access");
+        let receiver = Key::Hash(contract.receiver_package_hash.unwrap().value());
+        assert_eq!(
+            contract.call_share(
+                &contract.account("admin"),
+                "store_uref",
+                runtime_args! {
+                    "uref" => uref,
+                    "recipient" => receiver,
+                    "bundle" => "onboarding".to_string()
+                },
+            ),
+            Err(DeployError::User(123))
+        );
+    }
+
+    #[test]
+    fn unknown_bundle() {
+        let mut contract = deploy_with_bundle();
+
+        assert_eq!(
+            contract.claim_bundle_access(&contract.account("unauth"), "onboarding"),
+            Err(DeployError::User(117))
+        );
+    }
+
+    /// Deploys the contracts, the user picks up its access and names `guardians`.
+    fn deploy_with_guardians(guardians: &[&str], threshold: u8) -> Fixture {
+        let mut contract = FixtureBuilder::standalone().build();
+        let guardians: Vec<AccountHash> = guardians
+            .iter()
+            .map(|guardian| contract.account(guardian))
+            .collect();
+        contract.claim_access(&contract.account("user")).unwrap();
+        contract
+            .call_locked_entry_point(
+                &contract.account("user"),
+                "register_guardians",
+                runtime_args! {"guardians" => guardians, "threshold" => threshold},
+            )
+            .unwrap();
+        contract
+    }
+
+    /// `guardian` declares the user lost, moving its access to the contractor account.
+    fn declare_user_lost(contract: &mut Fixture, guardian: &str) -> Outcome {
+        let guardian = contract.account(guardian);
+        let args = runtime_args! {
+            "account" => contract.account("user"),
+            "new_account" => contract.account("contractor"),
+            "share_contract" => contract.share_package_hash()
+        };
+        contract.call_locked_entry_point(&guardian, "declare_lost", args)
+    }
+
+    #[test]
+    fn recovered_access() {
+        let mut contract = deploy_with_guardians(&["unauth"], 1);
+
+        declare_user_lost(&mut contract, "unauth").unwrap();
+        contract
+            .claim_access(&contract.account("contractor"))
+            .unwrap();
+        assert_eq!(
+            contract.call_protected(&contract.account("contractor")),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn recovery_revokes_old_access() {
+        let mut contract = deploy_with_guardians(&["unauth"], 1);
+
+        // The URef left in the lost account no longer grants access.
+        declare_user_lost(&mut contract, "unauth").unwrap();
+        assert_eq!(
+            contract.call_protected(&contract.account("user")),
+            Err(DeployError::InvalidContext)
+        );
+    }
+
+    #[test]
+    fn recovery_below_threshold() {
+        let mut contract = deploy_with_guardians(&["unauth", "admin"], 2);
+
+        // A single guardian is not enough, nothing is deposited for the new account.
+        declare_user_lost(&mut contract, "unauth").unwrap();
+        assert_eq!(
+            contract.claim_access(&contract.account("contractor")),
+            Err(DeployError::User(1))
+        );
+    }
+
+    #[test]
+    fn recovery_by_non_guardian() {
+        let mut contract = deploy_with_guardians(&["unauth"], 1);
+
+        assert_eq!(
+            declare_user_lost(&mut contract, "admin"),
+            Err(DeployError::User(9))
+        );
+    }
+
+    #[test]
+    fn recovery_through_other_share_contract() {
+        let mut contract = deploy_with_guardians(&["unauth"], 1);
+
+        // The fresh URef only ever goes to the sharing contract the contract was installed with.
+        let args = runtime_args! {
+            "account" => contract.account("user"),
+            "new_account" => contract.account("contractor"),
+            "share_contract" => contract.locked_package_hash
+        };
+        assert_eq!(
+            contract.call_locked_entry_point(&contract.account("unauth"), "declare_lost", args),
+            Err(DeployError::User(11))
+        );
+    }
+
+    /// Deploys the contracts and has the owner charge a deposit fee of `fee` motes.
+    fn deploy_with_fee(fee: u64) -> Fixture {
+        let mut contract = FixtureBuilder::standalone().build();
+        contract
+            .call_share(
+                &contract.account("admin"),
+                "set_fee",
+                runtime_args! {"fee" => U512::from(fee), "fee_expiry" => 0u64},
+            )
+            .unwrap();
+        contract
+    }
+
+    #[test]
+    fn fee_without_purse() {
+        let mut contract = deploy_with_fee(1_000);
+
+        assert_eq!(
+            contract.deposit(
+                &contract.account("admin"),
+                "locked_deployer_admin_access",
+                Key::Account(contract.account("unauth")),
+            ),
+            Err(DeployError::User(115))
+        );
+    }
+
+    #[test]
+    fn fee_refunded_on_claim() {
+        let mut contract = deploy_with_fee(1_000);
+
+        // The fee is held by the contract while the entry is pending.
+        contract
+            .deposit_with_fee(
+                &contract.account("admin"),
+                "locked_deployer_admin_access",
+                Key::Account(contract.account("unauth")),
+            )
+            .unwrap();
+        assert_eq!(contract.fee_purse_balance(), U512::from(1_000));
+
+        contract
+            .claim(&contract.account("unauth"), None, None)
+            .unwrap();
+        assert_eq!(contract.fee_purse_balance(), U512::zero());
+    }
+
+    #[test]
+    fn fee_refunded_on_reclaim() {
+        let mut contract = deploy_with_fee(1_000);
+
+        contract
+            .deposit_with_fee(
+                &contract.account("admin"),
+                "locked_deployer_admin_access",
+                Key::Account(contract.account("unauth")),
+            )
+            .unwrap();
+        contract
+            .call_share(
+                &contract.account("admin"),
+                "reclaim_uref",
+                runtime_args! {"recipient" => Key::Account(contract.account("unauth"))},
+            )
+            .unwrap();
+        assert_eq!(contract.fee_purse_balance(), U512::zero());
+    }
+
+    #[test]
+    fn install_with_fee() {
+        // The installer sets aside a budget for the deposit fees, the rest goes back.
+        let mut contract = FixtureBuilder::standalone()
+            .share_args(runtime_args! {"fee" => U512::from(1_000), "fee_expiry" => 0u64})
+            .locked_args(runtime_args! {"fee_budget" => U512::from(10_000)})
+            .build();
+        // The three admin URefs for accounts are paid for.
+        assert_eq!(contract.fee_purse_balance(), U512::from(3_000));
+
+        // Claiming the access settles the entry and refunds its fee.
+        contract.claim_access(&contract.account("user")).unwrap();
+        assert_eq!(contract.fee_purse_balance(), U512::from(2_000));
+        assert_eq!(contract.call_protected(&contract.account("user")), Ok(()));
+    }
+
+    #[test]
+    fn install_with_fee_without_budget() {
+        let install = FixtureBuilder::standalone()
+            .share_args(runtime_args! {"fee" => U512::from(1_000), "fee_expiry" => 0u64})
+            .try_build();
+        assert_eq!(install.err(), Some(DeployError::User(115)));
+    }
+
+    #[test]
+    fn fee_purse_kept_across_upgrade() {
+        let mut contract = deploy_with_fee(1_000);
+
+        contract
+            .deposit_with_fee(
+                &contract.account("admin"),
+                "locked_deployer_admin_access",
+                Key::Account(contract.account("unauth")),
+            )
+            .unwrap();
+        contract.upgrade_share(RuntimeArgs::new()).unwrap();
+        // The held fee stays where the new version refunds it from.
+        assert_eq!(contract.fee_purse_balance(), U512::from(1_000));
+
+        contract
+            .claim(&contract.account("unauth"), None, None)
+            .unwrap();
+        assert_eq!(contract.fee_purse_balance(), U512::zero());
+    }
+
+    #[test]
+    fn delegation_fee() {
+        let mut contract = deploy_with_fee(1_000);
+        let user = contract.account("user");
+        contract.claim_access(&user).unwrap();
+
+        // The delegator pays the fee like any depositor, and gets it back once the delegation is revoked.
+        assert_eq!(
+            contract.delegate(&user, &contract.public_key("unauth")),
+            Err(DeployError::User(115))
+        );
+        contract
+            .delegate_with_fee(&user, &contract.public_key("unauth"))
+            .unwrap();
+        assert_eq!(contract.fee_purse_balance(), U512::from(1_000));
+
+        contract
+            .revoke(&user, &contract.public_key("unauth"))
+            .unwrap();
+        assert_eq!(contract.fee_purse_balance(), U512::zero());
+    }
+
+    #[test]
+    fn ownership_handover_without_fee() {
+        let mut contract = deploy_with_fee(1_000);
+
+        // The handover entries are not charged, the owner passes no purse.
+        let user = contract.account("user");
+        contract
+            .transfer_share_ownership(&contract.account("admin"), &user)
+            .unwrap();
+        assert_eq!(contract.fee_purse_balance(), U512::zero());
+        contract.accept_share_ownership(&user).unwrap();
+        contract
+            .call_share(&user, "pause", runtime_args! {})
+            .unwrap();
+    }
+
+    #[test]
+    fn reclaim_by_other_account() {
+        let mut contract = deploy_with_fee(1_000);
+
+        // Only the depositor can take an entry back.
+        contract
+            .deposit_with_fee(
+                &contract.account("admin"),
+                "locked_deployer_admin_access",
+                Key::Account(contract.account("unauth")),
+            )
+            .unwrap();
+        assert_eq!(
+            contract.call_share(
+                &contract.account("user"),
+                "reclaim_uref",
+                runtime_args! {"recipient" => Key::Account(contract.account("unauth"))},
+            ),
+            Err(DeployError::User(116))
+        );
+    }
+
+    #[test]
+    fn pause_by_non_owner() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::standalone().build();
+
+        // Only members of the owner group can pause.
+        assert_eq!(
+            contract.call_share(&contract.account("user"), "pause", runtime_args! {}),
+            Err(DeployError::InvalidContext)
+        );
+    }
+
+    #[test]
+    fn ownership_handover() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::standalone().build();
+
+        // Hand the contract over to the user, the access URefs travel through the vault.
+        let user = contract.account("user");
+        contract
+            .transfer_share_ownership(&contract.account("admin"), &user)
+            .unwrap();
+        contract.accept_share_ownership(&user).unwrap();
+
+        // The new owner is in charge, the previous one is not anymore.
+        contract
+            .call_share(&user, "pause", runtime_args! {})
+            .unwrap();
+        assert_eq!(
+            contract.call_share(&contract.account("admin"), "unpause", runtime_args! {}),
+            Err(DeployError::InvalidContext)
+        );
+    }
+
+    #[test]
+    fn ownership_handover_while_paused() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::standalone().build();
+
+        // The handover still works while deposits and claims are frozen.
+        let user = contract.account("user");
+        contract
+            .call_share(&contract.account("admin"), "pause", runtime_args! {})
+            .unwrap();
+        contract
+            .transfer_share_ownership(&contract.account("admin"), &user)
+            .unwrap();
+        contract.accept_share_ownership(&user).unwrap();
+
+        contract
+            .call_share(&user, "unpause", runtime_args! {})
+            .unwrap();
+        contract.claim_access(&user).unwrap();
+        assert_eq!(contract.call_protected(&user), Ok(()));
+    }
+
+    #[test]
+    fn ownership_transfer_superseded() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::standalone().build();
+
+        // The user claims the handover entries, then the owner hands the contract to someone else.
+        let user = contract.account("user");
+        let unauth = contract.account("unauth");
+        let share_package_hash = contract.share_package_hash();
+        contract
+            .transfer_share_ownership(&contract.account("admin"), &user)
+            .unwrap();
+        contract
+            .claim(&user, Some(share_package_hash), None)
+            .unwrap();
+        contract
+            .transfer_share_ownership(&contract.account("admin"), &unauth)
+            .unwrap();
+
+        // The owner URef of the superseded handover no longer works, nor can it be accepted.
+        assert_eq!(
+            contract.call_share(&user, "pause", runtime_args! {}),
+            Err(DeployError::InvalidContext)
+        );
+        let package_access = contract.named_uref(&user, "package-access");
+        assert_eq!(
+            contract.call_share(
+                &user,
+                "accept_ownership",
+                runtime_args! {"package_access" => package_access},
+            ),
+            Err(DeployError::User(110))
+        );
+        contract.accept_share_ownership(&unauth).unwrap();
+    }
+
+    #[test]
+    fn ownership_transfer_superseded_before_claim() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::standalone().build();
+
+        // Entries of a superseded handover are taken back before the user claims them.
+        let user = contract.account("user");
+        let share_package_hash = contract.share_package_hash();
+        contract
+            .transfer_share_ownership(&contract.account("admin"), &user)
+            .unwrap();
+        contract
+            .transfer_share_ownership(&contract.account("admin"), &contract.account("unauth"))
+            .unwrap();
+        assert_eq!(
+            contract.claim(&user, Some(share_package_hash), None),
+            Err(DeployError::User(1))
+        );
+    }
+
+    #[test]
+    fn paused_claim_of_handover_by_other() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::standalone().build();
+
+        // Only the pending owner may claim entries of the sharing contract while paused.
+        let share_package_hash = contract.share_package_hash();
+        contract
+            .call_share(&contract.account("admin"), "pause", runtime_args! {})
+            .unwrap();
+        contract
+            .transfer_share_ownership(&contract.account("admin"), &contract.account("user"))
+            .unwrap();
+        assert_eq!(
+            contract.claim(&contract.account("unauth"), Some(share_package_hash), None),
+            Err(DeployError::User(111))
+        );
+    }
+
+    #[test]
+    fn ownership_accepted_by_other() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::standalone().build();
+
+        let user = contract.account("user");
+        contract
+            .transfer_share_ownership(&contract.account("admin"), &user)
+            .unwrap();
+        assert_eq!(
+            contract.call_share(
+                &contract.account("unauth"),
+                "accept_ownership",
+                runtime_args! {},
+            ),
+            Err(DeployError::User(110))
+        );
+    }
+
+    #[test]
+    fn unpinned_claim_after_share_upgrade() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::standalone().build();
+
+        // The new version of the sharing contract takes over the named keys of the old one,
+        // so the URef deposited before the upgrade is claimed from it.
+        contract.upgrade_share(RuntimeArgs::new()).unwrap();
+        let user = contract.account("user");
+        contract.claim_access(&user).unwrap();
+        assert_eq!(contract.call_protected(&user), Ok(()));
+    }
+
+    #[test]
+    fn pinned_claim_after_share_upgrade() {
+        // Deploy contracts, pinned to the first version of the sharing contract.
+        let mut contract = FixtureBuilder::standalone()
+            .locked_args(runtime_args! {"share_version" => 1u32})
+            .build();
+
+        // The claim keeps calling the entry points of the first version.
+        contract.upgrade_share(RuntimeArgs::new()).unwrap();
+        let user = contract.account("user");
+        contract.claim_access(&user).unwrap();
+        assert_eq!(contract.call_protected(&user), Ok(()));
+    }
+
+    #[test]
+    fn pinned_to_missing_share_version() {
+        // There is no second version to deposit the URefs in.
+        let installed = FixtureBuilder::standalone()
+            .locked_args(runtime_args! {"share_version" => 2u32})
+            .try_build();
+        assert_eq!(
+            installed.err(),
+            Some(DeployError::InvalidContractVersion(2))
+        );
+    }
+
+    #[test]
+    fn claim_naming_bound_share_contract() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::standalone().build();
+
+        // Naming the sharing contract the URefs were deposited in is the same as naming none.
+        let user = contract.account("user");
+        let args = runtime_args! {
+            "this_contract" => contract.locked_package_hash,
+            "share_contract" => contract.share_package_hash()
+        };
+        contract
+            .call_locked_entry_point(&user, "get_access", args)
+            .unwrap();
+        assert_eq!(contract.call_protected(&user), Ok(()));
+    }
+
+    #[test]
+    fn claim_naming_other_share_contract() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::standalone().build();
+
+        // Any other package is turned down before it is called.
+        let args = runtime_args! {
+            "this_contract" => contract.locked_package_hash,
+            "share_contract" => contract.locked_package_hash
+        };
+        assert_eq!(
+            contract.call_locked_entry_point(&contract.account("user"), "get_access", args),
+            Err(DeployError::User(11))
+        );
     }
 
     // Integrated version tests
     // These are the same tests as with the standalone version but the uref storage feature is written and
     // available from inside the same context.
 
-    use super::integrated::IntegratedContarct;
-
     #[test]
-    #[should_panic(expected = "ApiError::User(777)")]
     fn integrated_deployer_calls_function() {
         // Deploy contracts.
-        let mut contract = IntegratedContarct::deploy();
+        let mut contract = FixtureBuilder::integrated().build();
         // Admin calls contract, has access rights to it, and so can
-        contract.call_locked(&contract.admin.clone());
+        assert_eq!(contract.call_protected(&contract.account("admin")), Ok(()));
     }
 
     #[test]
-    #[should_panic(expected = "InvalidContext")]
     fn integrated_unauth_calls_function() {
         // Deploy contracts.
-        let mut contract = IntegratedContarct::deploy();
+        let mut contract = FixtureBuilder::integrated().build();
 
         // Call restricted function with user who does not hava access to it,
         // and so the call reverts with InvalidContext error.
-        contract.call_locked(&contract.user.clone());
+        assert_eq!(
+            contract.call_protected(&contract.account("user")),
+            Err(DeployError::InvalidContext)
+        );
     }
 
     #[test]
-    #[should_panic(expected = "ApiError::User(777)")]
     fn integrated_getting_access() {
         // Deploy contracts.
-        let mut contract = IntegratedContarct::deploy();
+        let mut contract = FixtureBuilder::integrated().build();
 
         // User retrieves access rights to the function.
-        contract.retrieve_urefs(&contract.user.clone());
+        contract.claim_access(&contract.account("user")).unwrap();
         // User now has access rights to call the access restricted function.
-        contract.call_locked(&contract.user.clone());
-
-        // Test does not reach this unreachable,
-        // since we managed to call the contract that reverts with User(777) error.
-        unreachable!();
+        assert_eq!(contract.call_protected(&contract.account("user")), Ok(()));
     }
 
     #[test]
-    #[should_panic(expected = "User(1)")]
     fn integrated_unable_to_gain_access() {
         // Deploy contracts.
-        let mut contract = IntegratedContarct::deploy();
-
-        // User tries to retrieve access rights to the function.
-        contract.retrieve_urefs(&contract.unauth.clone());
+        let mut contract = FixtureBuilder::integrated().build();
 
         // This user was not designated on deployment to recieve access rights,
         // so they recieve User(1) error, which means they would have gotten the "default" URef value.
-        contract.call_locked(&contract.unauth.clone());
-        unreachable!();
+        assert_eq!(
+            contract.claim_access(&contract.account("unauth")),
+            Err(DeployError::User(1))
+        );
+
+        // Without the URef the call is turned away by the engine.
+        assert_eq!(
+            contract.call_protected(&contract.account("unauth")),
+            Err(DeployError::InvalidContext)
+        );
+    }
+
+    #[test]
+    fn integrated_release_requires_approval() {
+        // Deploy contracts, with the admin as the only approver.
+        let mut contract = FixtureBuilder::integrated()
+            .locked_args(runtime_args! {
+                "approvers" => vec![account_key(1)],
+                "approval_threshold" => 1u8
+            })
+            .build();
+
+        assert_eq!(
+            contract.claim_access(&contract.account("user")),
+            Err(DeployError::User(5))
+        );
+    }
+
+    #[test]
+    fn integrated_release_after_approval() {
+        // Deploy contracts, with the admin as the only approver.
+        let mut contract = FixtureBuilder::integrated()
+            .locked_args(runtime_args! {
+                "approvers" => vec![account_key(1)],
+                "approval_threshold" => 1u8
+            })
+            .build();
+
+        contract
+            .approve_release(&contract.account("admin"), &contract.public_key("user"))
+            .unwrap();
+        contract.claim_access(&contract.account("user")).unwrap();
+        assert_eq!(contract.call_protected(&contract.account("user")), Ok(()));
+    }
+
+    #[test]
+    fn integrated_release_by_distinct_approvers() {
+        // Deploy contracts, the admin and the unauthorized account both have to approve.
+        let mut contract = FixtureBuilder::integrated()
+            .locked_args(runtime_args! {
+                "approvers" => vec![account_key(1), account_key(3)],
+                "approval_threshold" => 2u8
+            })
+            .build();
+        let user = contract.account("user");
+
+        // Approving twice still counts as one approval.
+        for _ in 0..2 {
+            contract
+                .approve_release(&contract.account("admin"), &contract.public_key("user"))
+                .unwrap();
+        }
+        assert_eq!(contract.claim_access(&user), Err(DeployError::User(5)));
+
+        // The second approver holds no admin URef, and does not need one to approve.
+        contract
+            .approve_release(&contract.account("unauth"), &contract.public_key("user"))
+            .unwrap();
+        contract.claim_access(&user).unwrap();
+        assert_eq!(contract.call_protected(&user), Ok(()));
+    }
+
+    #[test]
+    fn integrated_approval_by_non_approver() {
+        let mut contract = FixtureBuilder::integrated()
+            .locked_args(runtime_args! {
+                "approvers" => vec![account_key(1)],
+                "approval_threshold" => 1u8
+            })
+            .build();
+
+        // Recipients can not approve their own URefs.
+        assert_eq!(
+            contract.approve_release(&contract.account("user"), &contract.public_key("user")),
+            Err(DeployError::User(12))
+        );
+    }
+
+    #[test]
+    fn integrated_approval_does_not_cover_later_urefs() {
+        let mut contract = FixtureBuilder::integrated()
+            .locked_args(runtime_args! {
+                "approvers" => vec![account_key(1)],
+                "approval_threshold" => 1u8
+            })
+            .build();
+        let user = contract.account("user");
+        contract
+            .approve_release(&contract.account("admin"), &contract.public_key("user"))
+            .unwrap();
+
+        // A URef stored after the approval is held back, the approved one is still released.
+        contract.request_access(&user, "admin").unwrap();
+        contract
+            .approve_access(&contract.account("admin"), &user)
+            .unwrap();
+        contract.claim_access(&user).unwrap();
+        assert_eq!(contract.call_protected(&user), Ok(()));
+    }
+
+    #[test]
+    fn integrated_release_approved_by_admins() {
+        let mut contract = FixtureBuilder::integrated()
+            .locked_args(runtime_args! {
+                "approval_threshold" => 1u8,
+                "admin_approvers" => true
+            })
+            .build();
+        let user = contract.account("user");
+        assert_eq!(contract.claim_access(&user), Err(DeployError::User(5)));
+
+        // The admin approves with the URef it holds.
+        let uref = contract.named_uref(&contract.account("admin"), "locked_deployer_admin_access");
+        contract
+            .call_locked_entry_point(
+                &contract.account("admin"),
+                "approve_release",
+                runtime_args! {"recipient" => Key::Account(user), "uref" => uref},
+            )
+            .unwrap();
+        contract.claim_access(&user).unwrap();
+        assert_eq!(contract.call_protected(&user), Ok(()));
+
+        // Any other URef fails the group check.
+        let uref = contract.main_purse(&contract.account("unauth"));
+        assert_eq!(
+            contract.call_locked_entry_point(
+                &contract.account("unauth"),
+                "approve_release",
+                runtime_args! {"recipient" => Key::Account(user), "uref" => uref},
+            ),
+            Err(DeployError::InvalidContext)
+        );
+    }
+
+    #[test]
+    fn integrated_threshold_above_approvers() {
+        // Two approvals can never be gathered from a single approver.
+        let install = FixtureBuilder::integrated()
+            .locked_args(runtime_args! {
+                "approvers" => vec![account_key(1)],
+                "approval_threshold" => 2u8
+            })
+            .try_build();
+        assert_eq!(install.err(), Some(DeployError::User(13)));
+    }
+
+    #[test]
+    fn integrated_approved_access_request() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::integrated().build();
+
+        // The unauthorized account asks for access and an admin approves it.
+        contract
+            .request_access(&contract.account("unauth"), "admin")
+            .unwrap();
+        contract
+            .approve_access(&contract.account("admin"), &contract.account("unauth"))
+            .unwrap();
+
+        // The freshly provisioned URef is picked up like any other.
+        contract.claim_access(&contract.account("unauth")).unwrap();
+        assert_eq!(contract.call_protected(&contract.account("unauth")), Ok(()));
+    }
+
+    #[test]
+    fn integrated_list_access_requests() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::integrated().build();
+
+        contract
+            .request_access(&contract.account("unauth"), "admin")
+            .unwrap();
+
+        // The admin reads the pending request back, note included.
+        assert_eq!(
+            contract.access_requests(&contract.account("admin")),
+            Ok(vec![(
+                contract.account("unauth"),
+                "admin".to_string(),
+                "test request".to_string()
+            )])
+        );
+
+        // Once approved it is no longer pending.
+        contract
+            .approve_access(&contract.account("admin"), &contract.account("unauth"))
+            .unwrap();
+        assert_eq!(
+            contract.access_requests(&contract.account("admin")),
+            Ok(vec![])
+        );
+    }
+
+    #[test]
+    fn integrated_access_request_note_too_long() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::integrated().build();
+
+        let note = "x".repeat(257);
+        assert_eq!(
+            contract.request_access_with_note(&contract.account("unauth"), "admin", &note),
+            Err(DeployError::User(14))
+        );
+    }
+
+    #[test]
+    fn integrated_denied_access_request() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::integrated().build();
+
+        contract
+            .request_access(&contract.account("unauth"), "admin")
+            .unwrap();
+        contract
+            .deny_access(&contract.account("admin"), &contract.account("unauth"))
+            .unwrap();
+
+        // Nothing was stored for the account.
+        assert_eq!(
+            contract.claim_access(&contract.account("unauth")),
+            Err(DeployError::User(1))
+        );
+    }
+
+    #[test]
+    fn integrated_request_unknown_role() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::integrated().build();
+
+        assert_eq!(
+            contract.request_access(&contract.account("unauth"), "owner"),
+            Err(DeployError::User(6))
+        );
+    }
+
+    #[test]
+    fn integrated_approve_without_request() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::integrated().build();
+
+        assert_eq!(
+            contract.approve_access(&contract.account("admin"), &contract.account("unauth")),
+            Err(DeployError::User(7))
+        );
+    }
+
+    #[test]
+    fn integrated_account_hash_recipient() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::integrated().build();
+
+        // The deployer shares its own URef, naming the recipient by account hash.
+        let unauth = contract.account("unauth");
+        contract
+            .deposit(
+                &contract.account("admin"),
+                "locked_deployer_admin_access",
+                Key::Account(unauth),
+            )
+            .unwrap();
+        contract.claim_access(&unauth).unwrap();
+        assert_eq!(contract.call_protected(&unauth), Ok(()));
+    }
+
+    #[test]
+    fn integrated_paused_claims() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::integrated().build();
+
+        contract
+            .call_locked_entry_point(&contract.account("admin"), "pause", runtime_args! {})
+            .unwrap();
+        assert_eq!(
+            contract.claim_access(&contract.account("user")),
+            Err(DeployError::User(11))
+        );
+    }
+
+    #[test]
+    fn integrated_ownership_handover() {
+        // Deploy contracts.
+        let mut contract = FixtureBuilder::integrated().build();
+
+        let user = contract.account("user");
+        contract
+            .call_locked_entry_point(
+                &contract.account("admin"),
+                "transfer_ownership",
+                runtime_args! {"new_owner" => user},
+            )
+            .unwrap();
+        contract
+            .call_locked_entry_point(&user, "accept_ownership", runtime_args! {})
+            .unwrap();
+        assert_eq!(
+            contract.call_locked_entry_point(&contract.account("admin"), "pause", runtime_args! {}),
+            Err(DeployError::User(9))
+        );
     }
 }
 
@@ -0,0 +1,305 @@
+// Property based scenarios for the standalone version.
+// Random sequences of deposits, claims, delegations, revocations, calls and upgrades
+// run against the engine, each step checked against a reference model of who holds access.
+use proptest::prelude::*;
+
+use super::fixture::{DeployError, Fixture, FixtureBuilder, Mode, Outcome, Role};
+use casper_types::{Key, RuntimeArgs};
+
+/// Accounts taking part in every scenario, steps refer to them by index.
+const ACCOUNTS: [(&str, Role); 4] = [
+    ("admin", Role::Deployer),
+    ("holder", Role::Holder),
+    ("second_holder", Role::Holder),
+    ("outsider", Role::Outsider),
+];
+
+const DEPLOYER: usize = 0;
+
+/// Depth the sharing contract allows delegation chains to grow to when installed without arguments.
+const MAX_DELEGATION_DEPTH: u8 = 1;
+
+/// One deploy of a scenario.
+#[derive(Clone, Copy, Debug)]
+enum Step {
+    /// The first account shares the URef it holds with the second one.
+    Deposit(usize, usize),
+    /// The account claims what is pending for it with `get_access`, preferring the URef the testing
+    /// contract deposited over one deposited directly.
+    Claim(usize),
+    /// The account calls the access restricted function.
+    Call(usize),
+    /// The first account passes the URef it claimed on to the second one.
+    Delegate(usize, usize),
+    /// The first account withdraws the delegation made to the second one.
+    Revoke(usize, usize),
+    /// The deployer installs a new version of the sharing contract.
+    Upgrade,
+}
+
+impl Step {
+    fn run(self, fixture: &mut Fixture) -> Outcome {
+        let name = |account: usize| ACCOUNTS[account].0;
+        match self {
+            Step::Deposit(from, to) => {
+                let uref_name = match from {
+                    DEPLOYER => "locked_deployer_admin_access",
+                    _ => "admin",
+                };
+                let recipient = Key::Account(fixture.account(name(to)));
+                fixture.deposit(&fixture.account(name(from)), uref_name, recipient)
+            }
+            Step::Claim(account) => fixture.claim_access(&fixture.account(name(account))),
+            Step::Call(account) => fixture.call_protected(&fixture.account(name(account))),
+            Step::Delegate(from, to) => {
+                fixture.delegate(&fixture.account(name(from)), &fixture.public_key(name(to)))
+            }
+            Step::Revoke(by, target) => fixture.revoke(
+                &fixture.account(name(by)),
+                &fixture.public_key(name(target)),
+            ),
+            Step::Upgrade => fixture.upgrade_share(RuntimeArgs::new()),
+        }
+    }
+}
+
+fn account() -> impl Strategy<Value = usize> {
+    0..ACCOUNTS.len()
+}
+
+fn step() -> impl Strategy<Value = Step> {
+    prop_oneof![
+        3 => (account(), account()).prop_map(|(from, to)| Step::Deposit(from, to)),
+        3 => account().prop_map(Step::Claim),
+        3 => account().prop_map(Step::Call),
+        2 => (account(), account()).prop_map(|(from, to)| Step::Delegate(from, to)),
+        2 => (account(), account()).prop_map(|(by, target)| Step::Revoke(by, target)),
+        1 => Just(Step::Upgrade),
+    ]
+}
+
+/// Where an entry was deposited from, the sharing contract keeps one entry per source and label.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Source {
+    /// Deposited by the testing contract on install, tagged with its package.
+    Install,
+    /// Deposited with `store_uref` without a source package.
+    Direct,
+}
+
+/// URefs are told apart by a number, the account they were issued to on install
+/// and the ones after for the URefs the testing contract issues to delegates.
+type Entry = (Source, usize);
+
+/// What the sharing contract and the accounts should hold, mirroring the contract logic.
+#[derive(Debug)]
+struct Model {
+    /// Entries pending for each account, oldest first.
+    pending: Vec<Vec<Entry>>,
+    /// Entries each account claimed from the sharing contract, which still counts them as held.
+    held: Vec<Vec<Entry>>,
+    /// URef each account stores under `admin`, once it claimed one.
+    claimed: Vec<Option<usize>>,
+    /// Delegator, depth and URef of the delegation each account received. Only the `admin` entry the
+    /// testing contract deposited can be delegated, so there is at most one.
+    delegations: Vec<Option<(usize, u8, usize)>>,
+    /// Accounts each account delegated to.
+    delegates: Vec<Vec<usize>>,
+    /// Number of the next URef the testing contract issues to a delegate.
+    next_uref: usize,
+    /// URefs the testing contract took back from the `admin` group on a revocation.
+    revoked: Vec<usize>,
+}
+
+impl Model {
+    /// The state right after install, every holder has its URef pending.
+    fn new() -> Self {
+        Model {
+            pending: ACCOUNTS
+                .iter()
+                .enumerate()
+                .map(|(account, (_, role))| match role {
+                    Role::Holder => vec![(Source::Install, account)],
+                    _ => vec![],
+                })
+                .collect(),
+            held: vec![vec![]; ACCOUNTS.len()],
+            claimed: vec![None; ACCOUNTS.len()],
+            delegations: vec![None; ACCOUNTS.len()],
+            delegates: vec![vec![]; ACCOUNTS.len()],
+            next_uref: ACCOUNTS.len(),
+            revoked: vec![],
+        }
+    }
+
+    /// Applies `step` and returns its expected outcome, or `None` if the account does not hold
+    /// the URef the step is made with, in which case the step is skipped.
+    fn apply(&mut self, step: Step) -> Option<Outcome> {
+        let outcome = match step {
+            Step::Deposit(from, to) => {
+                let uref = match from {
+                    DEPLOYER => DEPLOYER,
+                    _ => self.claimed[from]?,
+                };
+                self.deposit(to, (Source::Direct, uref));
+                Ok(())
+            }
+            Step::Claim(account) => self.claim(account),
+            Step::Call(account) if account == DEPLOYER => Ok(()),
+            Step::Call(account) => match self.claimed[account] {
+                Some(uref) if !self.revoked.contains(&uref) => Ok(()),
+                _ => Err(DeployError::InvalidContext),
+            },
+            Step::Delegate(from, to) => {
+                let uref = self.claimed[from]?;
+                self.delegate(from, to, uref)
+            }
+            Step::Revoke(by, target) => self.revoke(by, target),
+            // The new version takes the named keys of the previous one along, so pending
+            // entries, claims and delegations all carry over.
+            Step::Upgrade => Ok(()),
+        };
+        Some(outcome)
+    }
+
+    /// Entries tagged with the testing contract are claimed on their own, untagged ones only while
+    /// none is pending and the account holds no URef yet.
+    fn claim(&mut self, account: usize) -> Outcome {
+        let pending = &mut self.pending[account];
+        if let Some(index) = pending
+            .iter()
+            .position(|(source, _)| *source == Source::Install)
+        {
+            let entry = pending.remove(index);
+            self.claimed[account] = Some(entry.1);
+            self.held[account].push(entry);
+            return Ok(());
+        }
+        if self.claimed[account].is_some() {
+            return Err(DeployError::User(1));
+        }
+        match pending.last() {
+            Some(&(_, uref)) => {
+                self.claimed[account] = Some(uref);
+                let pending = pending.drain(..).collect::<Vec<_>>();
+                self.held[account].extend(pending);
+                Ok(())
+            }
+            None => Err(DeployError::User(1)),
+        }
+    }
+
+    fn deposit(&mut self, account: usize, (source, uref): Entry) {
+        self.pending[account].retain(|(pending_source, _)| *pending_source != source);
+        self.pending[account].push((source, uref));
+    }
+
+    fn delegate(&mut self, from: usize, to: usize, uref: usize) -> Outcome {
+        match self.entries(from).find(|(_, held)| *held == uref) {
+            Some((Source::Install, _)) => {}
+            Some((Source::Direct, _)) => return Err(DeployError::User(120)),
+            None => return Err(DeployError::User(100)),
+        }
+        let depth = self.depth(from) + 1;
+        if depth > MAX_DELEGATION_DEPTH {
+            return Err(DeployError::User(101));
+        }
+        if self.delegations[to].is_some()
+            || self
+                .entries(to)
+                .any(|(held_source, _)| *held_source == Source::Install)
+        {
+            return Err(DeployError::User(102));
+        }
+        if self.ancestors(from).contains(&to) {
+            return Err(DeployError::User(121));
+        }
+        let issued = self.next_uref;
+        self.next_uref += 1;
+        self.deposit(to, (Source::Install, issued));
+        self.delegations[to] = Some((from, depth, issued));
+        if !self.delegates[from].contains(&to) {
+            self.delegates[from].push(to);
+        }
+        Ok(())
+    }
+
+    fn revoke(&mut self, by: usize, target: usize) -> Outcome {
+        if self.delegations[target].is_none() {
+            return Err(DeployError::User(103));
+        }
+        if !self.ancestors(target).contains(&by) {
+            return Err(DeployError::User(104));
+        }
+        self.remove_delegation(target);
+        Ok(())
+    }
+
+    fn remove_delegation(&mut self, account: usize) {
+        let (delegator, _, uref) = match self.delegations[account].take() {
+            Some(delegation) => delegation,
+            None => return,
+        };
+        for delegate in self.delegates[account].clone() {
+            if self.delegator(delegate) == Some(account) {
+                self.remove_delegation(delegate);
+            }
+        }
+        self.delegates[delegator].retain(|delegate| *delegate != account);
+        self.pending[account].retain(|(_, held)| *held != uref);
+        self.held[account].retain(|(_, held)| *held != uref);
+        self.revoked.push(uref);
+    }
+
+    /// The accounts the entry was delegated to `account` through, its delegator first.
+    fn ancestors(&self, account: usize) -> Vec<usize> {
+        let mut ancestors = vec![];
+        let mut current = account;
+        while let Some(delegator) = self.delegator(current) {
+            ancestors.push(delegator);
+            current = delegator;
+        }
+        ancestors
+    }
+
+    /// Every entry stored for `account`, pending or already claimed.
+    fn entries(&self, account: usize) -> impl Iterator<Item = &Entry> {
+        self.pending[account]
+            .iter()
+            .chain(self.held[account].iter())
+    }
+
+    fn delegator(&self, account: usize) -> Option<usize> {
+        self.delegations[account].map(|(delegator, _, _)| delegator)
+    }
+
+    fn depth(&self, account: usize) -> u8 {
+        self.delegations[account].map_or(0, |(_, depth, _)| depth)
+    }
+}
+
+fn deploy() -> Fixture {
+    ACCOUNTS
+        .iter()
+        .fold(
+            FixtureBuilder::new(Mode::Standalone),
+            |builder, (name, role)| builder.user(name, *role),
+        )
+        .build()
+}
+
+proptest! {
+    // Every case deploys the contracts anew, keep the number of cases low.
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn scenarios_match_model(steps in prop::collection::vec(step(), 1..24)) {
+        let mut fixture = deploy();
+        let mut model = Model::new();
+        for step in steps {
+            if let Some(expected) = model.apply(step) {
+                prop_assert_eq!(step.run(&mut fixture), expected, "at {:?}", step);
+            }
+        }
+    }
+}
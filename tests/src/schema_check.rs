@@ -0,0 +1,549 @@
+//! Checks the entry points every contract declares in `uref_share_client::schema` against their
+//! bodies in `uref_share/src`. Every named argument an entry point reads, itself or through the
+//! functions of the contract it calls, has to be declared with the type it is read as, and the value
+//! it passes to `runtime::ret` has to have the type it declares to return. The sources are parsed as
+//! they are, so the declarations can't drift from the code they describe.
+//! Access and entry point types don't show in the bodies, the integration tests cover them.
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::PathBuf,
+};
+
+use casper_types::{
+    account::AccountHash, contracts::ContractVersion, CLType, CLTyped, ContractPackageHash,
+    EntryPoints,
+};
+use syn::{
+    visit::{self, Visit},
+    Expr, ExprCall, ExprForLoop, ExprLit, ExprPath, File, GenericArgument, Item, ItemFn, Lit,
+    Local, Pat, PatIdent, Path, PathArguments, Type,
+};
+use uref_share_client::schema;
+
+/// Reads and parses the source file at `path`, relative to the repository root.
+fn parse(path: &str) -> File {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(path);
+    let source = fs::read_to_string(&path)
+        .unwrap_or_else(|error| panic!("can not read {}: {}", path.display(), error));
+    syn::parse_file(&source)
+        .unwrap_or_else(|error| panic!("can not parse {}: {}", path.display(), error))
+}
+
+/// The last identifier of `expr` if it is a path, `MAX_FEE` for `args::MAX_FEE`.
+fn path_name(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string()),
+        _ => None,
+    }
+}
+
+/// The values of the constants of `args` by name, every element for the lists of names.
+fn arg_names() -> BTreeMap<String, Vec<String>> {
+    let mut names: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for item in parse("uref_share_client/src/args.rs").items {
+        if let Item::Const(constant) = item {
+            let values = match &*constant.expr {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(value),
+                    ..
+                }) => vec![value.value()],
+                Expr::Array(array) => array
+                    .elems
+                    .iter()
+                    .flat_map(|element| {
+                        path_name(element)
+                            .and_then(|name| names.get(&name))
+                            .unwrap_or_else(|| {
+                                panic!("args::{} lists an unknown name", constant.ident)
+                            })
+                            .clone()
+                    })
+                    .collect(),
+                _ => panic!(
+                    "args::{} is neither a name nor a list of them",
+                    constant.ident
+                ),
+            };
+            names.insert(constant.ident.to_string(), values);
+        }
+    }
+    names
+}
+
+/// The type aliases declared in `files`, by name.
+fn aliases(files: &[&File]) -> BTreeMap<String, Type> {
+    files
+        .iter()
+        .flat_map(|file| file.items.iter())
+        .filter_map(|item| match item {
+            Item::Type(alias) => Some((alias.ident.to_string(), (*alias.ty).clone())),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The first generic argument of the last segment of `ty`, `T` for `Option<T>`.
+fn type_argument(ty: &Type) -> Option<&Type> {
+    let segment = match ty {
+        Type::Path(path) => path.path.segments.last()?,
+        _ => return None,
+    };
+    match &segment.arguments {
+        PathArguments::AngleBracketed(arguments) => match arguments.args.first()? {
+            GenericArgument::Type(argument) => Some(argument),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The `CLType` of `ty`, resolving `aliases`.
+fn cl_type(ty: &Type, aliases: &BTreeMap<String, Type>) -> Result<CLType, String> {
+    let path = match ty {
+        Type::Tuple(tuple) => {
+            let mut elements = tuple
+                .elems
+                .iter()
+                .map(|element| cl_type(element, aliases).map(Box::new))
+                .collect::<Result<Vec<_>, _>>()?;
+            return match elements.len() {
+                0 => Ok(CLType::Unit),
+                1 => Ok(CLType::Tuple1([elements.remove(0)])),
+                2 => Ok(CLType::Tuple2([elements.remove(0), elements.remove(0)])),
+                3 => Ok(CLType::Tuple3([
+                    elements.remove(0),
+                    elements.remove(0),
+                    elements.remove(0),
+                ])),
+                len => Err(format!("no CLType for tuples of {} elements", len)),
+            };
+        }
+        Type::Path(path) => &path.path,
+        _ => return Err("no CLType for a type other than a path or a tuple".to_string()),
+    };
+    let name = match path.segments.last() {
+        Some(segment) => segment.ident.to_string(),
+        None => return Err("no CLType for an empty path".to_string()),
+    };
+    if let Some(alias) = aliases.get(&name) {
+        return cl_type(alias, aliases);
+    }
+    let argument = || match type_argument(ty) {
+        Some(argument) => cl_type(argument, aliases).map(Box::new),
+        None => Err(format!("{} without a type argument", name)),
+    };
+    Ok(match name.as_str() {
+        "bool" => CLType::Bool,
+        "u8" => CLType::U8,
+        "u32" => CLType::U32,
+        "u64" => CLType::U64,
+        "U512" => CLType::U512,
+        "String" => CLType::String,
+        "Key" => CLType::Key,
+        "URef" => CLType::URef,
+        "PublicKey" => CLType::PublicKey,
+        "AccountHash" => AccountHash::cl_type(),
+        "ContractPackageHash" => ContractPackageHash::cl_type(),
+        "ContractVersion" => ContractVersion::cl_type(),
+        "Option" => CLType::Option(argument()?),
+        "Vec" => CLType::List(argument()?),
+        _ => return Err(format!("no CLType known for {}", name)),
+    })
+}
+
+/// `expr` without the `unwrap…` calls made on it, and whether there were any.
+fn strip_unwraps(mut expr: &Expr) -> (&Expr, bool) {
+    let mut unwrapped = false;
+    while let Expr::MethodCall(call) = expr {
+        if !call.method.to_string().starts_with("unwrap") {
+            break;
+        }
+        expr = &call.receiver;
+        unwrapped = true;
+    }
+    (expr, unwrapped)
+}
+
+/// If `call` reads a named argument, whether it is an optional one,
+/// and the type it is read as if given with a turbofish.
+fn read_call(call: &ExprCall) -> Option<(bool, Option<&Type>)> {
+    let segment = match &*call.func {
+        Expr::Path(path) => path.path.segments.last()?,
+        _ => return None,
+    };
+    let optional = match segment.ident.to_string().as_str() {
+        "get_named_arg" => false,
+        "get_optional_named_arg" => true,
+        _ => return None,
+    };
+    let turbofish = match &segment.arguments {
+        PathArguments::AngleBracketed(arguments) => match arguments.args.first() {
+            Some(GenericArgument::Type(ty)) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    };
+    Some((optional, turbofish))
+}
+
+/// Whether `call` is `runtime::ret(…)`.
+fn is_ret(call: &ExprCall) -> bool {
+    match &*call.func {
+        Expr::Path(path) => {
+            let names: Vec<String> = path
+                .path
+                .segments
+                .iter()
+                .map(|segment| segment.ident.to_string())
+                .collect();
+            names == ["runtime", "ret"]
+        }
+        _ => false,
+    }
+}
+
+/// The names `function` binds, its parameters and variables.
+fn bindings(function: &ItemFn) -> BTreeSet<String> {
+    struct Bindings(BTreeSet<String>);
+    impl<'ast> Visit<'ast> for Bindings {
+        fn visit_pat_ident(&mut self, binding: &'ast PatIdent) {
+            self.0.insert(binding.ident.to_string());
+            visit::visit_pat_ident(self, binding);
+        }
+    }
+    let mut bindings = Bindings(BTreeSet::new());
+    bindings.visit_item_fn(function);
+    bindings.0
+}
+
+/// What an entry point does with its arguments and return value, as found in its body.
+#[derive(Default)]
+struct Body {
+    /// Named arguments read, with the type they are read as.
+    args: BTreeMap<String, CLType>,
+    /// Type of the value passed to `runtime::ret`, if any.
+    ret: Option<CLType>,
+}
+
+/// Walks the body of an entry point and of every function of the contract it calls or passes on.
+struct Reader<'ast> {
+    entry_point: String,
+    functions: &'ast BTreeMap<String, &'ast ItemFn>,
+    names: &'ast BTreeMap<String, Vec<String>>,
+    aliases: &'ast BTreeMap<String, Type>,
+    /// Functions walked already.
+    walked: BTreeSet<String>,
+    /// Loop variables running over a list of names of `args`, with the names.
+    loop_names: BTreeMap<String, Vec<String>>,
+    /// Names the function being walked binds, which refer to variables rather than functions.
+    bindings: BTreeSet<String>,
+    /// Types of the annotated `let` bindings of the function being walked, by name.
+    locals: BTreeMap<String, Type>,
+    /// Types of the reads whose type comes from the `let` binding they initialize, by address.
+    hints: BTreeMap<usize, Type>,
+    body: Body,
+    errors: Vec<String>,
+}
+
+impl<'ast> Reader<'ast> {
+    fn walk(&mut self, name: &str) {
+        if !self.walked.insert(name.to_string()) {
+            return;
+        }
+        let function = self.functions[name];
+        let bindings = std::mem::replace(&mut self.bindings, bindings(function));
+        let locals = std::mem::take(&mut self.locals);
+        self.visit_block(&function.block);
+        self.bindings = bindings;
+        self.locals = locals;
+    }
+
+    /// The function `path` names, if no variable shadows it.
+    fn function_name(&self, path: &Path) -> Option<String> {
+        let name = path.get_ident()?.to_string();
+        if self.functions.contains_key(&name) && !self.bindings.contains(&name) {
+            Some(name)
+        } else {
+            None
+        }
+    }
+
+    fn error(&mut self, message: String) {
+        self.errors
+            .push(format!("{} {}", self.entry_point, message));
+    }
+
+    /// The argument names `expr` stands for, a constant of `args` or a loop variable running over them.
+    fn arg_names(&self, expr: &Expr) -> Option<Vec<String>> {
+        let path = match expr {
+            Expr::Path(path) => &path.path,
+            _ => return None,
+        };
+        match path.segments.len() {
+            1 => self.loop_names.get(&path.segments[0].ident.to_string()),
+            2 if path.segments[0].ident == "args" => {
+                self.names.get(&path.segments[1].ident.to_string())
+            }
+            _ => None,
+        }
+        .cloned()
+    }
+
+    fn record_read(&mut self, call: &ExprCall, turbofish: Option<&Type>) {
+        let names = match call.args.first().and_then(|name| self.arg_names(name)) {
+            Some(names) => names,
+            None => return self.error("reads an argument not named by `args`".to_string()),
+        };
+        let ty = match turbofish.or_else(|| self.hints.get(&(call as *const ExprCall as usize))) {
+            Some(ty) => ty.clone(),
+            None => {
+                return self.error(format!(
+                    "reads {:?} as a type the check can't tell, annotate the binding or add a turbofish",
+                    names
+                ))
+            }
+        };
+        let cl_type = match cl_type(&ty, self.aliases) {
+            Ok(cl_type) => cl_type,
+            Err(error) => return self.error(format!("reads {:?}: {}", names, error)),
+        };
+        for name in names {
+            match self.body.args.get(&name) {
+                Some(read) if *read != cl_type => self.error(format!(
+                    "reads {} both as {:?} and {:?}",
+                    name, read, cl_type
+                )),
+                _ => {
+                    self.body.args.insert(name, cl_type.clone());
+                }
+            }
+        }
+    }
+
+    fn record_ret(&mut self, call: &ExprCall) {
+        // runtime::ret(CLValue::from_t(value).unwrap_or_revert())
+        let value = call.args.first().map(|arg| strip_unwraps(arg).0);
+        let ty = match value {
+            Some(Expr::Call(from_t)) => from_t
+                .args
+                .first()
+                .and_then(path_name)
+                .and_then(|name| self.locals.get(&name))
+                .cloned(),
+            _ => None,
+        };
+        let cl_type = match ty.map(|ty| cl_type(&ty, self.aliases)) {
+            Some(Ok(cl_type)) => cl_type,
+            Some(Err(error)) => return self.error(format!("returns a value: {}", error)),
+            None => {
+                return self
+                    .error("returns a value other than an annotated `let` binding".to_string())
+            }
+        };
+        match &self.body.ret {
+            Some(ret) if *ret != cl_type => {
+                let message = format!("returns both {:?} and {:?}", ret, cl_type);
+                self.error(message)
+            }
+            _ => self.body.ret = Some(cl_type),
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for Reader<'ast> {
+    fn visit_local(&mut self, local: &'ast Local) {
+        if let (Pat::Type(binding), Some((_, init))) = (&local.pat, &local.init) {
+            if let Pat::Ident(name) = &*binding.pat {
+                self.locals
+                    .insert(name.ident.to_string(), (*binding.ty).clone());
+            }
+            if let (Expr::Call(call), unwrapped) = strip_unwraps(init) {
+                if let Some((optional, _)) = read_call(call) {
+                    // A required argument is read as the binding type, an optional one as well once
+                    // unwrapped, and as the type inside the `Option` of the binding otherwise.
+                    let ty = match (optional, unwrapped) {
+                        (false, false) | (true, true) => Some(&*binding.ty),
+                        (true, false) => type_argument(&binding.ty),
+                        (false, true) => None,
+                    };
+                    if let Some(ty) = ty {
+                        self.hints
+                            .insert(call as *const ExprCall as usize, ty.clone());
+                    }
+                }
+            }
+        }
+        visit::visit_local(self, local);
+    }
+
+    fn visit_expr_call(&mut self, call: &'ast ExprCall) {
+        if let Some((_, turbofish)) = read_call(call) {
+            self.record_read(call, turbofish);
+        } else if is_ret(call) {
+            self.record_ret(call);
+        }
+        visit::visit_expr_call(self, call);
+    }
+
+    // Functions are followed where they are called and where they are passed on, as in `for_each(settle)`.
+    fn visit_expr_path(&mut self, path: &'ast ExprPath) {
+        if let Some(name) = self.function_name(&path.path) {
+            self.walk(&name);
+        }
+        visit::visit_expr_path(self, path);
+    }
+
+    fn visit_expr_for_loop(&mut self, for_loop: &'ast ExprForLoop) {
+        // for quota in args::QUOTAS.iter()
+        let list = match &*for_loop.expr {
+            Expr::MethodCall(call) if call.method == "iter" => &*call.receiver,
+            expr => expr,
+        };
+        let bound = match (&for_loop.pat, self.arg_names(list)) {
+            (Pat::Ident(binding), Some(names)) => {
+                let name = binding.ident.to_string();
+                self.loop_names.insert(name.clone(), names);
+                Some(name)
+            }
+            _ => None,
+        };
+        visit::visit_expr_for_loop(self, for_loop);
+        if let Some(name) = bound {
+            self.loop_names.remove(&name);
+        }
+    }
+}
+
+/// What the entry points of the contract in `source` read and return, by name.
+/// Entry points are the `#[no_mangle]` functions but `call`.
+fn bodies(
+    source: &File,
+    names: &BTreeMap<String, Vec<String>>,
+    aliases: &BTreeMap<String, Type>,
+    errors: &mut Vec<String>,
+) -> BTreeMap<String, Body> {
+    let functions: BTreeMap<String, &ItemFn> = source
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Fn(function) => Some((function.sig.ident.to_string(), function)),
+            _ => None,
+        })
+        .collect();
+    functions
+        .iter()
+        .filter(|(name, function)| {
+            *name != "call"
+                && function
+                    .attrs
+                    .iter()
+                    .any(|attribute| attribute.path.is_ident("no_mangle"))
+        })
+        .map(|(name, _)| {
+            let mut reader = Reader {
+                entry_point: name.clone(),
+                functions: &functions,
+                names,
+                aliases,
+                walked: BTreeSet::new(),
+                loop_names: BTreeMap::new(),
+                bindings: BTreeSet::new(),
+                locals: BTreeMap::new(),
+                hints: BTreeMap::new(),
+                body: Body::default(),
+                errors: Vec::new(),
+            };
+            reader.walk(name);
+            errors.extend(reader.errors);
+            (name.clone(), reader.body)
+        })
+        .collect()
+}
+
+/// Differences between the entry points declared and the bodies, one line each.
+fn differences(
+    contract: &str,
+    declared: EntryPoints,
+    mut bodies: BTreeMap<String, Body>,
+) -> Vec<String> {
+    let mut differences = Vec::new();
+    for entry_point in declared.take_entry_points() {
+        let name = entry_point.name();
+        let body = match bodies.remove(name) {
+            Some(body) => body,
+            None => {
+                differences.push(format!("{} declares {}, which has no body", contract, name));
+                continue;
+            }
+        };
+        let parameters: BTreeMap<String, CLType> = entry_point
+            .args()
+            .iter()
+            .map(|parameter| (parameter.name().to_string(), parameter.cl_type().clone()))
+            .collect();
+        for (arg, read) in body.args.iter() {
+            match parameters.get(arg) {
+                Some(cl_type) if cl_type == read => {}
+                Some(cl_type) => differences.push(format!(
+                    "{} {} reads {} as {:?}, but declares it as {:?}",
+                    contract, name, arg, read, cl_type
+                )),
+                None => differences.push(format!(
+                    "{} {} reads {} as {:?}, but does not declare it",
+                    contract, name, arg, read
+                )),
+            }
+        }
+        for arg in parameters
+            .keys()
+            .filter(|arg| !body.args.contains_key(*arg))
+        {
+            differences.push(format!(
+                "{} {} declares {}, but never reads it",
+                contract, name, arg
+            ));
+        }
+        let ret = body.ret.unwrap_or(CLType::Unit);
+        if *entry_point.ret() != ret {
+            differences.push(format!(
+                "{} {} returns {:?}, but declares {:?}",
+                contract,
+                name,
+                ret,
+                entry_point.ret()
+            ));
+        }
+    }
+    for name in bodies.keys() {
+        differences.push(format!("{} does not declare {}", contract, name));
+    }
+    differences
+}
+
+#[test]
+fn declared_entry_points_match_their_bodies() {
+    let names = arg_names();
+    let client = parse("uref_share_client/src/lib.rs");
+    let decode = parse("uref_share_client/src/decode.rs");
+    let contracts: Vec<(&str, EntryPoints)> = vec![
+        ("share", schema::share()),
+        ("locked", schema::locked()),
+        ("locked_with_share", schema::locked_with_share()),
+        ("receiver", schema::receiver()),
+    ];
+    let mut mismatches = Vec::new();
+    for (contract, entry_points) in contracts {
+        let source = parse(&format!("uref_share/src/{}.rs", contract));
+        let aliases = aliases(&[&client, &decode, &source]);
+        let bodies = bodies(&source, &names, &aliases, &mut mismatches);
+        mismatches.extend(differences(contract, entry_points, bodies));
+    }
+    assert!(mismatches.is_empty(), "{}", mismatches.join("\n"));
+}
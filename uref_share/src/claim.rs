@@ -0,0 +1,37 @@
+#![no_main]
+extern crate alloc;
+
+mod named_args;
+
+use contract::contract_api::{runtime, runtime::revert};
+use types::{contracts::ContractPackageHash, runtime_args, ApiError, Key, RuntimeArgs};
+use uref_share_client::{args, entry_points, keys, Entry};
+
+use named_args::{get_named_arg, get_optional_named_arg};
+
+/// Session code claiming every URef pending for the caller in a URef sharing contract,
+/// optionally only the ones from `source_package` or stored under `label`.
+/// Each claimed URef is stored in the callers account under its label. When the entry names
+/// the package it grants access to, and no `source_package` filter was given,
+/// the label is prefixed with that package so URefs of several contracts do not collide.
+#[no_mangle]
+fn call() {
+    let share_hash: ContractPackageHash = get_named_arg(args::SHARE_HASH);
+    let source_package: Option<ContractPackageHash> = get_optional_named_arg(args::SOURCE_PACKAGE);
+    let label: Option<String> = get_optional_named_arg(args::LABEL);
+
+    let entries: Vec<Entry> = runtime::call_versioned_contract(
+        share_hash,
+        None,
+        entry_points::CLAIM_UREFS,
+        runtime_args! {args::SOURCE_PACKAGE => source_package, args::LABEL => label},
+    );
+    if entries.is_empty() {
+        revert(ApiError::User(1));
+    }
+
+    for (source, label, uref) in entries {
+        let source = source.filter(|_| source_package.is_none());
+        runtime::put_key(&keys::claimed_uref(source, &label), Key::URef(uref));
+    }
+}
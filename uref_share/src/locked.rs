@@ -1,64 +1,127 @@
 #![no_main]
 extern crate alloc;
 
+mod named_args;
+
+use std::convert::{TryFrom, TryInto};
+
 use contract::{
-    contract_api::{runtime, runtime::revert, storage},
+    contract_api::{account, runtime, runtime::revert, storage, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
 use types::{
-    contracts::ContractPackageHash, runtime_args, ApiError, CLType, EntryPoint, EntryPointAccess,
-    EntryPointType, EntryPoints, Key, Parameter, PublicKey, RuntimeArgs, URef,
+    account::AccountHash,
+    bytesrepr::{FromBytes, ToBytes},
+    contracts::{ContractPackageHash, ContractVersion, NamedKeys},
+    runtime_args, ApiError, CLTyped, CLValue, Key, PublicKey, RuntimeArgs, URef, U512,
+};
+use uref_share_client::{
+    args, decode::ShareBinding, entry_points, keys, schema, Entry, DEFAULT_LABEL,
+    SHARE_INTERFACE_VERSION,
 };
 
-pub fn prepare_access(contract_package_hash: &ContractPackageHash) {
+use named_args::{get_named_arg, get_optional_named_arg};
+
+/// An issued `admin` group URef, as `(holder, uref, lease end)`.
+/// The lease end is a block time, `0` for URefs that do not lapse.
+type Holder = (Key, URef, u64);
+
+/// Creates the `admin` group and hands its URefs out through the sharing contract.
+/// Returns the holder registry.
+pub fn prepare_access(
+    contract_package_hash: &ContractPackageHash,
+    lease_duration: u64,
+    (share_contract, share_version): ShareBinding,
+) -> Vec<Holder> {
     // Get list of public keys of the potential admins
-    let users: Vec<PublicKey> = runtime::get_named_arg("users");
-    // Get the package hash for the uref share contract
-    let share_contract: ContractPackageHash = runtime::get_named_arg("share_hash");
+    let users: Vec<PublicKey> = get_named_arg(args::USERS);
+    // Get list of account hashes of potential admins we do not know the public key of
+    let user_hashes: Vec<AccountHash> =
+        get_optional_named_arg(args::USER_HASHES).unwrap_or_default();
+    // Get list of contract packages that should be admins as well
+    let contracts: Vec<ContractPackageHash> =
+        get_optional_named_arg(args::CONTRACTS).unwrap_or_default();
+    // Get list of public keys of temporary admins, their access lapses after `lease_duration`
+    let contractors: Vec<PublicKey> = get_optional_named_arg(args::CONTRACTORS).unwrap_or_default();
+    // Get the name of the bundle the URefs are deposited in, if any
+    let bundle: Option<String> = get_optional_named_arg(args::BUNDLE);
+    // Get the amount set aside for the deposit fees of the sharing contract, if it charges any
+    let fee_budget: U512 = get_optional_named_arg(args::FEE_BUDGET).unwrap_or_default();
+    ensure_compatible_share(share_contract, share_version);
+    let fee_purse = if fee_budget.is_zero() {
+        None
+    } else {
+        let fee_purse = system::create_purse();
+        system::transfer_from_purse_to_purse(
+            account::get_main_purse(),
+            fee_purse,
+            fee_budget,
+            None,
+        )
+        .unwrap_or_revert();
+        Some(fee_purse)
+    };
+
+    let lease_end = match lease_duration {
+        0 => 0,
+        lease_duration => u64::from(runtime::get_blocktime()) + lease_duration,
+    };
+    let recipients: Vec<(Key, u64)> = users
+        .iter()
+        .map(|user| Key::Account(user.to_account_hash()))
+        .chain(user_hashes.into_iter().map(Key::Account))
+        .chain(contracts.into_iter().map(Key::from))
+        .map(|recipient| (recipient, 0))
+        .chain(
+            contractors
+                .iter()
+                .map(|contractor| (Key::Account(contractor.to_account_hash()), lease_end)),
+        )
+        .collect();
 
     let mut admin_group = storage::create_contract_user_group(
         *contract_package_hash,
         "admin",
-        (users.len() + 1) as u8,
+        u8::try_from(recipients.len() + 1).unwrap_or_revert_with(ApiError::InvalidArgument),
         alloc::collections::BTreeSet::default(),
     )
     .unwrap_or_revert();
 
-    runtime::put_key(
-        "locked_deployer_admin_access",
-        Key::URef(admin_group.pop().unwrap_or_revert()),
-    );
+    let deployer_access = admin_group.pop().unwrap_or_revert();
+    runtime::put_key(keys::DEPLOYER_ADMIN_ACCESS, Key::URef(deployer_access));
+    let mut holders = vec![(Key::Account(runtime::get_caller()), deployer_access, 0)];
 
-    for user in users {
+    for (recipient, lease_end) in recipients {
+        let uref = admin_group.pop().unwrap_or_revert();
+        let mut args = runtime_args! {
+            args::UREF => uref,
+            args::RECIPIENT => recipient,
+            args::LABEL => String::from(DEFAULT_LABEL),
+            args::SOURCE_PACKAGE => *contract_package_hash
+        };
+        if let (Some(bundle), Key::Account(_)) = (&bundle, recipient) {
+            args.insert(args::BUNDLE, bundle.clone()).unwrap_or_revert();
+        }
+        if let Some(fee_purse) = fee_purse {
+            args.insert(args::PURSE, fee_purse).unwrap_or_revert();
+        }
         let _: () = runtime::call_versioned_contract(
             share_contract,
-            None,
-            "store_uref",
-            runtime_args! {"uref" => admin_group.pop().unwrap_or_revert(), "account_pubkey" => user},
+            share_version,
+            entry_points::STORE_UREF,
+            args,
         );
+        holders.push((recipient, uref, lease_end));
     }
-}
-
-/// Returns the list of the entry points in the contract with added group security.
-pub fn get_entry_points(contract_package_hash: &ContractPackageHash) -> EntryPoints {
-    prepare_access(contract_package_hash);
-    let mut entry_points = EntryPoints::new();
-    entry_points.add_entry_point(EntryPoint::new(
-        "group_access_only",
-        vec![],
-        CLType::URef,
-        EntryPointAccess::groups(&["admin"]),
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        "get_access",
-        vec![Parameter::new("share_contract".to_string(), CLType::URef)],
-        CLType::Unit,
-        EntryPointAccess::Public,
-        EntryPointType::Session,
-    ));
-    entry_points
+    // What the fees did not use goes back, refunds go to the installer as the depositor anyway.
+    if let Some(fee_purse) = fee_purse {
+        let rest = system::get_purse_balance(fee_purse).unwrap_or_revert();
+        if !rest.is_zero() {
+            system::transfer_from_purse_to_purse(fee_purse, account::get_main_purse(), rest, None)
+                .unwrap_or_revert();
+        }
+    }
+    holders
 }
 
 /// Deployer/upgrader function. Tries to retrieve any data presumably stored earlier
@@ -66,61 +129,592 @@ pub fn get_entry_points(contract_package_hash: &ContractPackageHash) -> EntryPoi
 /// otherwise creates a new contract.
 pub fn install_or_upgrade_contract(name: String) {
     let contract_package_hash: ContractPackageHash =
-        match runtime::get_key(&format!("{}-package-hash", name)) {
+        match runtime::get_key(&keys::package_hash(&name)) {
             Some(contract_package_hash) => {
                 contract_package_hash.into_hash().unwrap_or_revert().into()
             }
             None => {
                 let (contract_package_hash, access_token) =
                     storage::create_contract_package_at_hash();
-                runtime::put_key(
-                    &format!("{}-package-hash", name),
-                    contract_package_hash.into(),
-                );
+                runtime::put_key(&keys::package_hash(&name), contract_package_hash.into());
                 // Store package hash wrapped so we can use it in the test context
                 runtime::put_key(
-                    &format!("{}-wrapped-package-hash", name),
+                    &keys::wrapped_package_hash(&name),
                     storage::new_uref(contract_package_hash).into(),
                 );
-                runtime::put_key(&format!("{}-access-uref", name), access_token.into());
+                runtime::put_key(&keys::access_uref(&name), access_token.into());
                 contract_package_hash
             }
         };
 
-    let entry_points = get_entry_points(&contract_package_hash);
+    // Lease policy, URefs issued to `contractors` lapse after `lease_duration` milliseconds.
+    let lease_duration: u64 = get_optional_named_arg(args::LEASE_DURATION).unwrap_or_default();
+    let renewable: bool = get_optional_named_arg(args::RENEWABLE).unwrap_or_default();
+    // The sharing contract to deposit in, and the version of it to call, its latest one if not pinned.
+    // Its versions share the stored URefs but may differ in what their entry points do, so a pinned
+    // contract keeps claims and recoveries on the entry points it was installed against.
+    let share_contract: ContractPackageHash = get_named_arg(args::SHARE_HASH);
+    let share_version: Option<ContractVersion> = get_optional_named_arg(args::SHARE_VERSION);
+
+    let mut named_keys = NamedKeys::new();
+    // Filled in once the URefs are deposited, the installer keeps write access to it.
+    let holders = storage::new_uref(Vec::<Holder>::new());
+    named_keys.insert("holders".to_string(), holders.into());
+    // Call allowances per role, `0` or a missing role stands for unlimited calls.
+    // Only the deployer manages them.
+    let allowances: Vec<(String, u32)> =
+        get_optional_named_arg(args::ALLOWANCES).unwrap_or_default();
+    for (role, calls) in allowances {
+        named_keys.insert(allowance_key(&role), storage::new_uref(calls).into());
+    }
+    named_keys.insert(
+        "deployer".to_string(),
+        storage::new_uref(runtime::get_caller()).into(),
+    );
+    named_keys.insert(
+        "lease_duration".to_string(),
+        storage::new_uref(lease_duration).into(),
+    );
+    named_keys.insert("renewable".to_string(), storage::new_uref(renewable).into());
+    named_keys.insert(
+        "share_contract".to_string(),
+        storage::new_uref(share_contract).into(),
+    );
+    named_keys.insert(
+        "share_version".to_string(),
+        storage::new_uref(share_version).into(),
+    );
+    // Sweeping removes URefs from the `admin` group, for that the contract needs
+    // its own package hash and the package access URef in its context.
+    named_keys.insert(
+        "package_hash".to_string(),
+        storage::new_uref(contract_package_hash).into(),
+    );
+    named_keys.insert(
+        "package_access".to_string(),
+        runtime::get_key(&keys::access_uref(&name)).unwrap_or_revert(),
+    );
+
+    let entry_points = schema::locked();
     let (contract_hash, _) =
-        storage::add_contract_version(contract_package_hash, entry_points, Default::default());
+        storage::add_contract_version(contract_package_hash, entry_points, named_keys);
+
+    // The sharing contract confirms deposits with `confirm_deposit`, so they can only be made
+    // once the contract version is added.
+    storage::write(
+        holders,
+        prepare_access(
+            &contract_package_hash,
+            lease_duration,
+            (share_contract, share_version),
+        ),
+    );
 
     runtime::put_key(&name, contract_hash.into());
     runtime::put_key(
-        &format!("{}-wrapped", name),
+        &keys::wrapped(&name),
         storage::new_uref(contract_hash).into(),
     );
 }
 
 // Entry points
 
+/// Claims the admin URef from the sharing contract `this_contract` deposited it in,
+/// and stores it under `admin`. Entries tagged with `this_contract` are claimed first, untagged ones
+/// only when there is none and the caller holds no `admin` URef yet.
+/// Given a `bundle`, claims every URef of the bundle instead, each stored under its source package
+/// and label, so that either all of them are installed or none.
+/// Reverts with `11` if a `share_contract` is passed that is not the one `this_contract` deposits in,
+/// and with `10` if the version it is pinned to does not implement the interface this contract was
+/// built against.
 #[no_mangle]
 fn get_access() {
-    let share_contract: ContractPackageHash = runtime::get_named_arg("share_contract");
+    let this_contract: ContractPackageHash = get_named_arg(args::THIS_CONTRACT);
+    // Session code can't read the named keys of the contract, so it asks for them.
+    let (share_contract, share_version): ShareBinding = runtime::call_versioned_contract(
+        this_contract,
+        None,
+        entry_points::SHARE_BINDING,
+        runtime_args! {},
+    );
+    ensure_bound_share(share_contract);
+    ensure_compatible_share(share_contract, share_version);
 
-    let access: URef =
-        runtime::call_versioned_contract(share_contract, None, "retrieve_uref", runtime_args! {});
+    if let Some(bundle) = get_optional_named_arg::<String>(args::BUNDLE) {
+        let entries: Vec<Entry> = runtime::call_versioned_contract(
+            share_contract,
+            share_version,
+            entry_points::CLAIM_BUNDLE,
+            runtime_args! {args::BUNDLE => bundle},
+        );
+        for (source, label, uref) in entries {
+            runtime::put_key(&keys::claimed_uref(source, &label), Key::URef(uref));
+        }
+        return;
+    }
 
-    if access == URef::default() {
+    // Claiming settles the entries, refunding their fees and freeing their place in the quotas.
+    let claim_admin_entries = |source_package: Option<ContractPackageHash>| -> Vec<Entry> {
+        runtime::call_versioned_contract(
+            share_contract,
+            share_version,
+            entry_points::CLAIM_UREFS,
+            runtime_args! {
+                args::SOURCE_PACKAGE => source_package,
+                args::LABEL => Some(String::from(DEFAULT_LABEL))
+            },
+        )
+    };
+    // Entries tagged with this contract hold one of its group URefs, the sharing contract checks
+    // that on deposit, and a recipient has at most one of them pending under `admin`.
+    if let Some((_, _, access)) = claim_admin_entries(Some(this_contract)).pop() {
+        runtime::put_key(DEFAULT_LABEL, Key::URef(access));
+        return;
+    }
+    // Untagged entries, like the ones the first release deposited, can come from anyone. They are
+    // only claimed while none is tagged with this contract, and never replace a URef claimed before.
+    if runtime::get_key(DEFAULT_LABEL).is_some() {
         revert(ApiError::User(1));
     }
-
-    runtime::put_key("admin", Key::URef(access));
+    // `admin` URefs of other packages are kept apart, like `claim.wasm` does.
+    let mut access = None;
+    for (source, label, uref) in claim_admin_entries(None) {
+        match source {
+            None => access = Some(uref),
+            Some(_) => runtime::put_key(&keys::claimed_uref(source, &label), Key::URef(uref)),
+        }
+    }
+    match access {
+        Some(access) => runtime::put_key(DEFAULT_LABEL, Key::URef(access)),
+        None => revert(ApiError::User(1)),
+    }
 }
 
+/// Succeeds for callers with access. Returns normally, rather than reverting with a marker,
+/// so that the call counted against the `admin` allowance is kept.
 #[no_mangle]
 fn group_access_only() {
-    // JACKPOT revert with User error 777 to see without a doubt that we have access to this function.
-    revert(ApiError::User(777))
+    ensure_lease();
+    meter("admin");
+}
+
+/// Returns the roles of the caller. Only callable with access, so they always include `admin`.
+#[no_mangle]
+fn check_access() {
+    ensure_lease();
+    let caller = runtime::get_caller();
+    let mut roles: Vec<String> = vec!["admin".to_string()];
+    if is_deployer(&caller) {
+        roles.push("deployer".to_string());
+    }
+    runtime::ret(CLValue::from_t(roles).unwrap_or_revert())
+}
+
+/// Returns the roles the holder registry records for `account`, without requiring access.
+/// Accounts that hold no URef, or whose lease ended, get an empty list.
+#[no_mangle]
+fn roles_of() {
+    let account: AccountHash = get_named_arg(args::ACCOUNT);
+    let now = u64::from(runtime::get_blocktime());
+    let mut roles: Vec<String> = Vec::new();
+    if get_key::<Vec<Holder>>("holders")
+        .iter()
+        .any(|(holder, _, lease_end)| {
+            *holder == Key::Account(account) && (*lease_end == 0 || now < *lease_end)
+        })
+    {
+        roles.push("admin".to_string());
+    }
+    if is_deployer(&account) {
+        roles.push("deployer".to_string());
+    }
+    runtime::ret(CLValue::from_t(roles).unwrap_or_revert())
+}
+
+/// Account context function that stores the roles of the caller, see `check_access`, in the callers
+/// account under `roles`. Given an `account`, stores the ones `roles_of` returns for it instead.
+#[no_mangle]
+fn get_roles() {
+    let this_contract: ContractPackageHash = get_named_arg(args::THIS_CONTRACT);
+    let account: Option<AccountHash> = get_optional_named_arg(args::ACCOUNT);
+    let roles: Vec<String> = match account {
+        Some(account) => runtime::call_versioned_contract(
+            this_contract,
+            None,
+            entry_points::ROLES_OF,
+            runtime_args! {args::ACCOUNT => account},
+        ),
+        None => runtime::call_versioned_contract(
+            this_contract,
+            None,
+            entry_points::CHECK_ACCESS,
+            runtime_args! {},
+        ),
+    };
+    runtime::put_key(keys::ROLES, storage::new_uref(roles).into());
+}
+
+/// Protected entry point that succeeds while the caller has calls left.
+#[no_mangle]
+fn metered_access() {
+    ensure_lease();
+    meter("admin");
+}
+
+/// Sets how many protected entry point calls each holder of `role` is allowed, `0` for unlimited.
+#[no_mangle]
+fn set_allowance() {
+    ensure_lease();
+    let role: String = get_named_arg(args::ROLE);
+    let calls: u32 = get_named_arg(args::CALLS);
+    set_key(&allowance_key(&role), calls);
+}
+
+/// Allows `account` `calls` more protected entry point calls as `role`, on top of the role allowance.
+#[no_mangle]
+fn top_up() {
+    ensure_lease();
+    let account: AccountHash = get_named_arg(args::ACCOUNT);
+    let role: String = get_named_arg(args::ROLE);
+    let calls: u32 = get_named_arg(args::CALLS);
+    let key = top_up_key(&role, &account);
+    set_key(&key, get_key::<u32>(&key).saturating_add(calls));
+}
+
+/// Forgets the calls `account` made as `role` and any top ups it received.
+#[no_mangle]
+fn reset_usage() {
+    ensure_lease();
+    let account: AccountHash = get_named_arg(args::ACCOUNT);
+    let role: String = get_named_arg(args::ROLE);
+    set_key(&usage_key(&role, &account), 0u32);
+    set_key(&top_up_key(&role, &account), 0u32);
+}
+
+/// Lets a holder name the `guardians` who can recover its access, `threshold` of them together.
+/// Reverts with `7` if the caller is not in the holder registry and `8` for a threshold of `0`
+/// or larger than the number of guardians. Replaces earlier guardians and pending recoveries.
+#[no_mangle]
+fn register_guardians() {
+    let guardians: Vec<AccountHash> = get_named_arg(args::GUARDIANS);
+    let threshold: u8 = get_named_arg(args::THRESHOLD);
+    ensure_lease();
+    let caller = runtime::get_caller();
+
+    if !get_key::<Vec<Holder>>("holders")
+        .iter()
+        .any(|(holder, _, _)| *holder == Key::Account(caller))
+    {
+        revert(ApiError::User(7));
+    }
+    if threshold == 0 || threshold as usize > guardians.len() {
+        revert(ApiError::User(8));
+    }
+    set_key(&guardians_key(&caller), (guardians, threshold));
+    runtime::remove_key(&recovery_key(&caller));
+}
+
+/// A guardian of `account` declares it lost and votes to move its access to `new_account`.
+/// Once `threshold` guardians agree on the same new account, the URef of `account` is removed
+/// from the `admin` group and a fresh one is deposited for `new_account` in the sharing contract,
+/// keeping the lease of the old one, its deposit fee paid from the `purse` of the last guardian if one is due.
+/// Reverts with `9` if the caller is not a guardian of `account`, `7` if `account` is no longer in the
+/// holder registry and `11` if a `share_contract` is passed that is not the one the URefs are deposited in.
+#[no_mangle]
+fn declare_lost() {
+    let account: AccountHash = get_named_arg(args::ACCOUNT);
+    let new_account: AccountHash = get_named_arg(args::NEW_ACCOUNT);
+    let purse: Option<URef> = get_optional_named_arg(args::PURSE);
+    let share_contract: ContractPackageHash = get_key("share_contract");
+    ensure_bound_share(share_contract);
+    let guardian = runtime::get_caller();
+
+    let threshold = match get_optional_key::<(Vec<AccountHash>, u8)>(&guardians_key(&account)) {
+        Some((guardians, threshold)) if guardians.contains(&guardian) => threshold,
+        _ => revert(ApiError::User(9)),
+    };
+    // Votes for a different new account start the count over.
+    let mut votes =
+        match get_optional_key::<(AccountHash, Vec<AccountHash>)>(&recovery_key(&account)) {
+            Some((proposed, votes)) if proposed == new_account => votes,
+            _ => Vec::new(),
+        };
+    if !votes.contains(&guardian) {
+        votes.push(guardian);
+    }
+    if votes.len() < threshold as usize {
+        set_key(&recovery_key(&account), (new_account, votes));
+        return;
+    }
+
+    let contract_package_hash: ContractPackageHash = get_key("package_hash");
+    let mut holders: Vec<Holder> = get_key("holders");
+    let mut revoked = alloc::collections::BTreeSet::new();
+    let mut lease = 0;
+    holders.retain(|(holder, uref, lease_end)| {
+        if *holder == Key::Account(account) {
+            revoked.insert(*uref);
+            lease = *lease_end;
+            false
+        } else {
+            true
+        }
+    });
+    // The account left the registry since, e.g. its lease was swept.
+    if revoked.is_empty() {
+        revert(ApiError::User(7));
+    }
+    storage::remove_contract_user_group_urefs(contract_package_hash, "admin", revoked)
+        .unwrap_or_revert();
+
+    let uref = storage::provision_contract_user_group_uref(contract_package_hash, "admin")
+        .unwrap_or_revert();
+    let share_version: Option<ContractVersion> = get_key("share_version");
+    ensure_compatible_share(share_contract, share_version);
+    let mut args = runtime_args! {
+        args::UREF => uref,
+        args::RECIPIENT => Key::Account(new_account),
+        args::LABEL => String::from(DEFAULT_LABEL),
+        args::SOURCE_PACKAGE => contract_package_hash
+    };
+    if let Some(purse) = purse {
+        args.insert(args::PURSE, purse).unwrap_or_revert();
+    }
+    let _: () = runtime::call_versioned_contract(
+        share_contract,
+        share_version,
+        entry_points::STORE_UREF,
+        args,
+    );
+    holders.push((Key::Account(new_account), uref, lease));
+    set_key("holders", holders);
+    runtime::remove_key(&guardians_key(&account));
+    runtime::remove_key(&recovery_key(&account));
+}
+
+/// Removes the `admin` group URefs whose lease ended from the group and the holder registry.
+#[no_mangle]
+fn sweep_expired() {
+    let now = u64::from(runtime::get_blocktime());
+    let (expired, holders): (Vec<Holder>, Vec<Holder>) = get_key::<Vec<Holder>>("holders")
+        .into_iter()
+        .partition(|(_, _, lease_end)| *lease_end != 0 && now >= *lease_end);
+    if expired.is_empty() {
+        return;
+    }
+    let urefs = expired.into_iter().map(|(_, uref, _)| uref).collect();
+    storage::remove_contract_user_group_urefs(get_key("package_hash"), "admin", urefs)
+        .unwrap_or_revert();
+    set_key("holders", holders);
+}
+
+/// Extends the lease of the caller by `lease_duration`, counted from now.
+/// Reverts with `2` if leases are not `renewable`, `3` if the caller holds no lease
+/// and `4` if it already ended.
+#[no_mangle]
+fn renew() {
+    if !get_key::<bool>("renewable") {
+        revert(ApiError::User(2));
+    }
+    let caller = Key::Account(runtime::get_caller());
+    let now = u64::from(runtime::get_blocktime());
+    let mut holders: Vec<Holder> = get_key("holders");
+    let lease_end = match holders
+        .iter_mut()
+        .find(|(holder, _, lease_end)| *holder == caller && *lease_end != 0)
+    {
+        Some((_, _, lease_end)) => lease_end,
+        None => revert(ApiError::User(3)),
+    };
+    if now >= *lease_end {
+        revert(ApiError::User(4));
+    }
+    *lease_end = now + get_key::<u64>("lease_duration");
+    set_key("holders", holders);
+}
+
+/// Returns the sharing contract this contract deposits in, and the version of it it calls,
+/// `None` for its latest one.
+#[no_mangle]
+fn share_binding() {
+    let share_binding: ShareBinding = (get_key("share_contract"), get_key("share_version"));
+    runtime::ret(CLValue::from_t(share_binding).unwrap_or_revert())
+}
+
+/// Called by the sharing contract when asked to store `uref` tagged with this package.
+/// Only reachable if `uref` is an `admin` group URef, which is all there is to check.
+#[no_mangle]
+fn confirm_deposit() {
+    let _: URef = get_named_arg(args::UREF);
+}
+
+/// Called by the sharing contract when the holder of `uref` delegates it to `account`. Issues a fresh
+/// `admin` group URef for `account`, records it in the holder registry with the lease of `uref`,
+/// and returns it. Only reachable with an `admin` group URef. A holder could pass its own URef on
+/// anyway, unlike that one the issued URef can be taken back with `revoke_delegate_uref`.
+/// Reverts with `7` if `uref` is not in the holder registry and `12` if its lease ended.
+#[no_mangle]
+fn issue_delegate_uref() {
+    let uref: URef = get_named_arg(args::UREF);
+    let account: AccountHash = get_named_arg(args::ACCOUNT);
+    let now = u64::from(runtime::get_blocktime());
+    let mut holders: Vec<Holder> = get_key("holders");
+    let lease_end = match holders
+        .iter()
+        .find(|(_, held, _)| held.addr() == uref.addr())
+    {
+        Some((_, _, lease_end)) => *lease_end,
+        None => revert(ApiError::User(7)),
+    };
+    if lease_end != 0 && now >= lease_end {
+        revert(ApiError::User(12));
+    }
+
+    let issued: URef =
+        storage::provision_contract_user_group_uref(get_key("package_hash"), "admin")
+            .unwrap_or_revert();
+    holders.push((Key::Account(account), issued, lease_end));
+    set_key("holders", holders);
+    let mut delegated: Vec<URef> = get_key("delegated");
+    delegated.push(issued);
+    set_key("delegated", delegated);
+    runtime::ret(CLValue::from_t(issued).unwrap_or_revert())
+}
+
+/// Called by the sharing contract when a delegation is revoked, with the URef `issue_delegate_uref`
+/// issued for it. Removes it from the `admin` group and the holder registry, unless `sweep_expired`
+/// or `declare_lost` did already. Public, as passing `uref` takes holding it, but reverts with `13`
+/// for a URef that was not issued for a delegation.
+#[no_mangle]
+fn revoke_delegate_uref() {
+    let uref: URef = get_named_arg(args::UREF);
+    let mut delegated: Vec<URef> = get_key("delegated");
+    if !delegated.iter().any(|issued| issued.addr() == uref.addr()) {
+        revert(ApiError::User(13));
+    }
+    delegated.retain(|issued| issued.addr() != uref.addr());
+    set_key("delegated", delegated);
+
+    let (revoked, holders): (Vec<Holder>, Vec<Holder>) = get_key::<Vec<Holder>>("holders")
+        .into_iter()
+        .partition(|(_, held, _)| held.addr() == uref.addr());
+    if revoked.is_empty() {
+        return;
+    }
+    let urefs = revoked.into_iter().map(|(_, uref, _)| uref).collect();
+    storage::remove_contract_user_group_urefs(get_key("package_hash"), "admin", urefs)
+        .unwrap_or_revert();
+    set_key("holders", holders);
+}
+
+/// Reverts with `12` if every lease the holder registry records for the caller ended.
+/// The URef of an ended lease stays in the `admin` group until `sweep_expired` runs,
+/// so every entry point guarded by the group checks it.
+fn ensure_lease() {
+    let caller = Key::Account(runtime::get_caller());
+    let now = u64::from(runtime::get_blocktime());
+    let mut leases = get_key::<Vec<Holder>>("holders")
+        .into_iter()
+        .filter(|(holder, _, _)| *holder == caller)
+        .map(|(_, _, lease_end)| lease_end)
+        .peekable();
+    if leases.peek().is_some() && leases.all(|lease_end| lease_end != 0 && now >= lease_end) {
+        revert(ApiError::User(12));
+    }
+}
+
+/// Counts a call of the caller to an entry point guarded by `role`.
+/// Reverts with `5` once the caller used up its allowance.
+fn meter(role: &str) {
+    let caller = runtime::get_caller();
+    let used: u32 = get_key(&usage_key(role, &caller));
+    let allowance: u32 = get_key(&allowance_key(role));
+    if allowance != 0 && used >= allowance.saturating_add(get_key(&top_up_key(role, &caller))) {
+        revert(ApiError::User(5));
+    }
+    set_key(&usage_key(role, &caller), used.saturating_add(1));
+}
+
+/// Reverts with `11` if the caller passed a `share_contract` other than the one this contract
+/// deposits in.
+fn ensure_bound_share(share_contract: ContractPackageHash) {
+    if let Some(passed) = get_optional_named_arg::<ContractPackageHash>(args::SHARE_CONTRACT) {
+        if passed != share_contract {
+            revert(ApiError::User(11));
+        }
+    }
+}
+
+/// Reverts with `10` unless `share_version` of the sharing contract, its latest one for `None`,
+/// implements the interface this contract was built against.
+fn ensure_compatible_share(
+    share_contract: ContractPackageHash,
+    share_version: Option<ContractVersion>,
+) {
+    let interface: u32 = runtime::call_versioned_contract(
+        share_contract,
+        share_version,
+        entry_points::VERSION,
+        runtime_args! {},
+    );
+    if interface != SHARE_INTERFACE_VERSION {
+        revert(ApiError::User(10));
+    }
+}
+
+fn is_deployer(account: &AccountHash) -> bool {
+    get_optional_key::<AccountHash>("deployer") == Some(*account)
+}
+
+fn guardians_key(account: &AccountHash) -> String {
+    format!("guardians-{}", account)
+}
+
+fn recovery_key(account: &AccountHash) -> String {
+    format!("recovery-{}", account)
+}
+
+fn allowance_key(role: &str) -> String {
+    format!("allowance-{}", role)
+}
+
+fn usage_key(role: &str, account: &AccountHash) -> String {
+    format!("usage-{}-{}", role, account)
+}
+
+fn top_up_key(role: &str, account: &AccountHash) -> String {
+    format!("top-up-{}-{}", role, account)
+}
+
+/// Returns value of a named key `name` or the type default if the key does not exist.
+fn get_key<T: FromBytes + CLTyped + Default>(name: &str) -> T {
+    get_optional_key(name).unwrap_or_default()
+}
+
+/// Same as `get_key`, but tells apart a missing key from a stored default value.
+fn get_optional_key<T: FromBytes + CLTyped>(name: &str) -> Option<T> {
+    runtime::get_key(name).map(|value| {
+        let key = value.try_into().unwrap_or_revert();
+        storage::read(key).unwrap_or_revert().unwrap_or_revert()
+    })
+}
+
+/// Creates new storage key `name` and stores `value` to it.
+/// In case the key `name` already exists, overwrites it with the new data.
+fn set_key<T: ToBytes + CLTyped>(name: &str, value: T) {
+    match runtime::get_key(name) {
+        Some(key) => {
+            let key_ref = key.try_into().unwrap_or_revert();
+            storage::write(key_ref, value);
+        }
+        None => {
+            let key = storage::new_uref(value).into();
+            runtime::put_key(name, key);
+        }
+    }
 }
 
 #[no_mangle]
 fn call() {
-    install_or_upgrade_contract(String::from("locked"));
+    install_or_upgrade_contract(String::from(keys::LOCKED_CONTRACT));
 }
@@ -1,122 +1,129 @@
 #![no_main]
 extern crate alloc;
 
-use std::convert::TryInto;
+mod named_args;
+
+use std::convert::{TryFrom, TryInto};
 
 use contract::{
-    contract_api::{
-        runtime,
-        runtime::{get_named_arg, revert},
-        storage,
-    },
+    contract_api::{runtime, runtime::revert, storage},
     unwrap_or_revert::UnwrapOrRevert,
 };
 use types::{
+    account::AccountHash,
     bytesrepr::{FromBytes, ToBytes},
-    contracts::ContractPackageHash,
-    runtime_args, ApiError, CLType, CLTyped, CLValue, EntryPoint, EntryPointAccess, EntryPointType,
-    EntryPoints, Key, Parameter, PublicKey, RuntimeArgs, URef,
+    contracts::{ContractPackageHash, NamedKeys},
+    runtime_args, ApiError, CLTyped, CLValue, Key, PublicKey, RuntimeArgs, URef,
 };
+use uref_share_client::{args, decode::AccessRequest, entry_points, keys, schema};
+
+use named_args::{get_named_arg, get_optional_named_arg};
+
+/// Longest `note` an access request may carry, in bytes.
+const MAX_NOTE_LENGTH: usize = 256;
+/// The approvers of a URef stored for an account.
+type Approval = (URef, Vec<AccountHash>);
 
 pub fn prepare_access(contract_package_hash: &ContractPackageHash) -> (Vec<PublicKey>, Vec<URef>) {
     // Get list of public keys of the potential admins
-    let users: Vec<PublicKey> = runtime::get_named_arg("users");
+    let users: Vec<PublicKey> = get_named_arg(args::USERS);
 
     let mut admin_group = storage::create_contract_user_group(
         *contract_package_hash,
         "admin",
-        (users.len() + 1) as u8,
+        u8::try_from(users.len() + 1).unwrap_or_revert_with(ApiError::InvalidArgument),
         alloc::collections::BTreeSet::default(),
     )
     .unwrap_or_revert();
 
     runtime::put_key(
-        "locked_deployer_admin_access",
+        keys::DEPLOYER_ADMIN_ACCESS,
         Key::URef(admin_group.pop().unwrap_or_revert()),
     );
     (users, admin_group)
 }
 
-/// Returns the list of the entry points in the contract with added group security.
-pub fn get_entry_points() -> EntryPoints {
-    let mut entry_points = EntryPoints::new();
-    entry_points.add_entry_point(EntryPoint::new(
-        "group_access_only",
-        vec![],
-        CLType::Unit,
-        EntryPointAccess::groups(&["admin"]),
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        "get_access",
-        vec![Parameter::new("this_contract".to_string(), CLType::URef)],
-        CLType::Unit,
-        EntryPointAccess::Public,
-        EntryPointType::Session,
-    ));
-    entry_points.add_entry_point(EntryPoint::new(
-        "retrieve_urefs",
-        vec![],
-        CLType::List(Box::new(CLType::URef)),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-    entry_points.add_entry_point(EntryPoint::new(
-        "append_urefs",
-        vec![
-            Parameter::new("urefs".to_string(), CLType::List(Box::new(CLType::URef))),
-            Parameter::new(
-                "account_pubkeys".to_string(),
-                CLType::List(Box::new(CLType::PublicKey)),
-            ),
-        ],
-        CLType::Unit,
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-    entry_points
-}
-
 /// Deployer/upgrader function. Tries to retrieve any data presumably stored earlier
 /// in the context associated to to `name`. If there is data, proceeds with that,
 /// otherwise creates a new contract.
 pub fn install_or_upgrade_contract(name: String) {
     let contract_package_hash: ContractPackageHash =
-        match runtime::get_key(&format!("{}-package-hash", name)) {
+        match runtime::get_key(&keys::package_hash(&name)) {
             Some(contract_package_hash) => {
                 contract_package_hash.into_hash().unwrap_or_revert().into()
             }
             None => {
                 let (contract_package_hash, access_token) =
                     storage::create_contract_package_at_hash();
-                runtime::put_key(
-                    &format!("{}-package-hash", name),
-                    contract_package_hash.into(),
-                );
+                runtime::put_key(&keys::package_hash(&name), contract_package_hash.into());
                 // Store package hash wrapped so we can use it in the test context
                 runtime::put_key(
-                    &format!("{}-wrapped-package-hash", name),
+                    &keys::wrapped_package_hash(&name),
                     storage::new_uref(contract_package_hash).into(),
                 );
-                runtime::put_key(&format!("{}-access-uref", name), access_token.into());
+                runtime::put_key(&keys::access_uref(&name), access_token.into());
                 contract_package_hash
             }
         };
 
-    let entry_points = get_entry_points();
+    // Release policy, URefs need `approval_threshold` distinct approvals from the `approvers`.
+    // They are named apart from the admin group, as its members only get their URefs once released.
+    // With `admin_approvers` the members that hold their URef already approve as well.
+    let approvers: Vec<AccountHash> = get_optional_named_arg::<Vec<PublicKey>>(args::APPROVERS)
+        .unwrap_or_default()
+        .iter()
+        .map(PublicKey::to_account_hash)
+        .collect();
+    let approval_threshold: u8 =
+        get_optional_named_arg(args::APPROVAL_THRESHOLD).unwrap_or_default();
+    let admin_approvers: bool = get_optional_named_arg(args::ADMIN_APPROVERS).unwrap_or_default();
+    if !admin_approvers && approval_threshold as usize > approvers.len() {
+        revert(ApiError::User(13));
+    }
+    let mut named_keys = NamedKeys::new();
+    named_keys.insert("approvers".to_string(), storage::new_uref(approvers).into());
+    named_keys.insert(
+        "approval_threshold".to_string(),
+        storage::new_uref(approval_threshold).into(),
+    );
+    named_keys.insert(
+        "admin_approvers".to_string(),
+        storage::new_uref(admin_approvers).into(),
+    );
+    // The contract provisions group URefs for approved requests by itself,
+    // for that it needs its own package hash and the package access URef in its context.
+    named_keys.insert(
+        "package_hash".to_string(),
+        storage::new_uref(contract_package_hash).into(),
+    );
+    named_keys.insert(
+        "package_access".to_string(),
+        runtime::get_key(&keys::access_uref(&name)).unwrap_or_revert(),
+    );
+    // Administrative state, the installer owns the contract.
+    named_keys.insert(
+        "owner".to_string(),
+        storage::new_uref(runtime::get_caller()).into(),
+    );
+    named_keys.insert(
+        "pending_owner".to_string(),
+        storage::new_uref(None::<AccountHash>).into(),
+    );
+    named_keys.insert("paused".to_string(), storage::new_uref(false).into());
+
+    let entry_points = schema::locked_with_share();
     let (contract_hash, _) =
-        storage::add_contract_version(contract_package_hash, entry_points, Default::default());
+        storage::add_contract_version(contract_package_hash, entry_points, named_keys);
     let (users, admin_group) = prepare_access(&contract_package_hash);
     let _: () = runtime::call_versioned_contract(
         contract_package_hash,
         None,
-        "append_urefs",
-        runtime_args! {"urefs" => admin_group, "account_pubkeys" => users},
+        entry_points::APPEND_UREFS,
+        runtime_args! {args::UREFS => admin_group, args::ACCOUNT_PUBKEYS => users},
     );
     runtime::put_key(&name, contract_hash.into());
     runtime::put_key(
-        &format!("{}-wrapped-hash", name),
+        &keys::wrapped_hash(&name),
         storage::new_uref(contract_hash).into(),
     );
 }
@@ -125,38 +132,150 @@ pub fn install_or_upgrade_contract(name: String) {
 
 #[no_mangle]
 fn append_urefs() {
-    let urefs: Vec<URef> = get_named_arg("urefs");
-    let mut users: Vec<PublicKey> = get_named_arg("account_pubkeys");
+    ensure_not_paused();
+    let urefs: Vec<URef> = get_named_arg(args::UREFS);
+    let users = get_recipients();
     if urefs.len() != users.len() {
         revert(ApiError::User(3));
     }
 
-    for uref in urefs {
-        let user_key = users.pop().unwrap_or_revert().to_account_hash().to_string();
-        let mut personal_uref_list: Vec<URef> = get_key(&user_key);
-        personal_uref_list.push(uref);
-        set_key(&user_key, personal_uref_list);
+    for (user, uref) in users.iter().zip(urefs) {
+        deposit_uref(user, uref);
+    }
+}
+
+/// Records a pending request of the caller to join the `role` group.
+/// A later request from the same account replaces the earlier one.
+/// Reverts with `14` for a `note` longer than `MAX_NOTE_LENGTH` bytes.
+#[no_mangle]
+fn request_access() {
+    let role: String = get_named_arg(args::ROLE);
+    let note: String = get_named_arg(args::NOTE);
+    if role != "admin" {
+        revert(ApiError::User(6));
+    }
+    if note.len() > MAX_NOTE_LENGTH {
+        revert(ApiError::User(14));
+    }
+
+    let requester = runtime::get_caller();
+    let mut requests: Vec<AccountHash> = get_key("access_requests");
+    if !requests.contains(&requester) {
+        requests.push(requester);
+        set_key("access_requests", requests);
+    }
+    set_key(&request_key(&requester), (role, note));
+}
+
+/// Returns every pending request as `(account, role, note)`.
+#[no_mangle]
+fn list_access_requests() {
+    let requests: Vec<AccessRequest> = get_key::<Vec<AccountHash>>("access_requests")
+        .into_iter()
+        .map(|account| {
+            let (role, note): (String, String) = get_key(&request_key(&account));
+            (account, role, note)
+        })
+        .collect();
+    runtime::ret(CLValue::from_t(requests).unwrap_or_revert())
+}
+
+/// Account context function that calls `list_access_requests` and stores the pending requests
+/// in the callers account, under `access-requests`.
+#[no_mangle]
+fn get_access_requests() {
+    let this_contract_package: ContractPackageHash = get_named_arg(args::THIS_CONTRACT);
+    let requests: Vec<AccessRequest> = runtime::call_versioned_contract(
+        this_contract_package,
+        None,
+        entry_points::LIST_ACCESS_REQUESTS,
+        runtime_args! {},
+    );
+    runtime::put_key(keys::ACCESS_REQUESTS, storage::new_uref(requests).into());
+}
+
+/// Provisions a new URef for the group the account requested,
+/// and stores it so it can be picked up with `get_access`.
+#[no_mangle]
+fn approve_access() {
+    ensure_not_paused();
+    let account: AccountHash = get_named_arg(args::ACCOUNT);
+    let (role, _) = take_request(&account);
+    let contract_package_hash: ContractPackageHash = get_key("package_hash");
+    let uref = storage::provision_contract_user_group_uref(contract_package_hash, &role)
+        .unwrap_or_revert();
+    deposit_uref(&account, uref);
+}
+
+/// Drops the pending request of the account.
+#[no_mangle]
+fn deny_access() {
+    let account: AccountHash = get_named_arg(args::ACCOUNT);
+    take_request(&account);
+}
+
+/// Records the callers approval to release each URef stored for the given account.
+/// Approvals count per URef, a URef appended later needs approvals of its own.
+/// See `ensure_approver` for who may approve.
+#[no_mangle]
+fn approve_release() {
+    let user = get_recipient();
+    let approver = runtime::get_caller();
+    ensure_approver(&approver);
+
+    let mut approvals: Vec<Approval> = get_key(&approvals_key(&user));
+    for uref in get_key::<Vec<URef>>(&user.to_string()) {
+        match approvals.iter_mut().find(|(approved, _)| *approved == uref) {
+            Some((_, approvers)) if approvers.contains(&approver) => {}
+            Some((_, approvers)) => approvers.push(approver),
+            None => approvals.push((uref, vec![approver])),
+        }
     }
+    set_key(&approvals_key(&user), approvals);
 }
 
-/// Getter function for the stored URefs
+/// Returns the URefs stored for the caller that gathered the approvals required.
+/// Reverts with `1` if there are none stored and `5` if none of them gathered the approvals.
 #[no_mangle]
 fn retrieve_urefs() {
-    let urefs: Vec<URef> = get_key(&runtime::get_caller().to_string());
-    if urefs.is_empty() {
+    ensure_not_paused();
+    let caller = runtime::get_caller();
+    let stored: Vec<URef> = get_key(&caller.to_string());
+    if stored.is_empty() {
         revert(ApiError::User(1));
     }
+    let threshold = get_key::<u8>("approval_threshold") as usize;
+    let approvals: Vec<Approval> = get_key(&approvals_key(&caller));
+    let urefs: Vec<URef> = stored
+        .into_iter()
+        .filter(|uref| {
+            threshold == 0
+                || approvals
+                    .iter()
+                    .any(|(approved, approvers)| approved == uref && approvers.len() >= threshold)
+        })
+        .collect();
+    if urefs.is_empty() {
+        revert(ApiError::User(5));
+    }
     runtime::ret(CLValue::from_t(urefs).unwrap_or_revert())
 }
 
+/// Called by this contract to check an approver holds an `admin` URef, see `ensure_approver`.
+/// Only reachable if `uref` is an `admin` group URef, which is all there is to check.
+#[no_mangle]
+fn confirm_approver() {
+    let _: URef = get_named_arg(args::UREF);
+}
+
 /// Account context function that calls retrieve and then stores the received URefs.
 #[no_mangle]
 fn get_access() {
-    let this_contract_package: ContractPackageHash = runtime::get_named_arg("this_contract");
+    let this_contract_package: ContractPackageHash = get_named_arg(args::THIS_CONTRACT);
     let urefs: Vec<URef> = runtime::call_versioned_contract(
         this_contract_package,
         None,
-        "retrieve_urefs",
+        entry_points::RETRIEVE_UREFS,
         runtime_args! {},
     );
     if urefs.is_empty() {
@@ -170,23 +289,155 @@ fn get_access() {
     }
 }
 
+/// Succeeds for callers with access, the same as in the standalone edition.
+#[no_mangle]
+fn group_access_only() {}
+
+/// Stops all deposits and claims until `unpause` is called. Owner only.
 #[no_mangle]
-fn group_access_only() {
-    // JACKPOT revert with User error 777 to see without a doubt that we have access to this function.
-    revert(ApiError::User(777))
+fn pause() {
+    ensure_owner();
+    set_key("paused", true);
+}
+
+/// Lifts a `pause`. Owner only.
+#[no_mangle]
+fn unpause() {
+    ensure_owner();
+    set_key("paused", false);
+}
+
+/// First step of an ownership handover, names the account that may accept it. Owner only.
+/// The current owner stays in charge until the handover is accepted.
+#[no_mangle]
+fn transfer_ownership() {
+    ensure_owner();
+    let new_owner: AccountHash = get_named_arg(args::NEW_OWNER);
+    set_key("pending_owner", Some(new_owner));
+}
+
+/// Second step of an ownership handover, callable only by the account named in `transfer_ownership`.
+#[no_mangle]
+fn accept_ownership() {
+    let caller = runtime::get_caller();
+    if get_key::<Option<AccountHash>>("pending_owner") != Some(caller) {
+        revert(ApiError::User(10));
+    }
+    set_key("owner", caller);
+    set_key("pending_owner", None::<AccountHash>);
+}
+
+/// Reverts with `12` unless `approver` is listed in `approvers`, or `admin_approvers` is set and
+/// the caller passed a `uref` of the `admin` group. `confirm_approver` is guarded by the group,
+/// so calling it with `uref` fails for any other URef.
+fn ensure_approver(approver: &AccountHash) {
+    if get_key::<Vec<AccountHash>>("approvers").contains(approver) {
+        return;
+    }
+    let uref: Option<URef> = get_optional_named_arg(args::UREF);
+    match uref {
+        Some(uref) if get_key::<bool>("admin_approvers") => {
+            let _: () = runtime::call_versioned_contract(
+                get_key("package_hash"),
+                None,
+                entry_points::CONFIRM_APPROVER,
+                runtime_args! {args::UREF => uref},
+            );
+        }
+        _ => revert(ApiError::User(12)),
+    }
+}
+
+/// Reverts with `9` unless the caller owns the contract.
+fn ensure_owner() {
+    if get_optional_key::<AccountHash>("owner") != Some(runtime::get_caller()) {
+        revert(ApiError::User(9));
+    }
+}
+
+/// Reverts with `11` while the contract is paused.
+fn ensure_not_paused() {
+    if get_key::<bool>("paused") {
+        revert(ApiError::User(11));
+    }
+}
+
+/// Reads the recipient account from `account_pubkey`, `account_hash` or `recipient`, in that order.
+/// Public keys of any algorithm and `Key::Account` normalize to the account hash,
+/// any other `Key` variant reverts with `8`.
+fn get_recipient() -> AccountHash {
+    if let Some(public_key) = get_optional_named_arg::<PublicKey>(args::ACCOUNT_PUBKEY) {
+        return public_key.to_account_hash();
+    }
+    if let Some(account_hash) = get_optional_named_arg::<AccountHash>(args::ACCOUNT_HASH) {
+        return account_hash;
+    }
+    account_from_key(get_named_arg::<Key>(args::RECIPIENT))
+}
+
+/// List version of `get_recipient`, reading `account_pubkeys`, `account_hashes` or `recipients`.
+fn get_recipients() -> Vec<AccountHash> {
+    if let Some(public_keys) = get_optional_named_arg::<Vec<PublicKey>>(args::ACCOUNT_PUBKEYS) {
+        return public_keys.iter().map(PublicKey::to_account_hash).collect();
+    }
+    if let Some(account_hashes) = get_optional_named_arg::<Vec<AccountHash>>(args::ACCOUNT_HASHES) {
+        return account_hashes;
+    }
+    get_named_arg::<Vec<Key>>(args::RECIPIENTS)
+        .into_iter()
+        .map(account_from_key)
+        .collect()
+}
+
+fn account_from_key(key: Key) -> AccountHash {
+    match key {
+        Key::Account(account_hash) => account_hash,
+        _ => revert(ApiError::User(8)),
+    }
+}
+
+/// Appends `uref` to the URefs stored for `user`, it gathers approvals of its own.
+fn deposit_uref(user: &AccountHash, uref: URef) {
+    let mut personal_uref_list: Vec<URef> = get_key(&user.to_string());
+    personal_uref_list.push(uref);
+    set_key(&user.to_string(), personal_uref_list);
+}
+
+/// Removes the pending request of `account` and returns its `(role, note)`.
+fn take_request(account: &AccountHash) -> (String, String) {
+    let mut requests: Vec<AccountHash> = get_key("access_requests");
+    if !requests.contains(account) {
+        revert(ApiError::User(7));
+    }
+    requests.retain(|requester| requester != account);
+    set_key("access_requests", requests);
+
+    let request = get_key(&request_key(account));
+    runtime::remove_key(&request_key(account));
+    request
+}
+
+fn request_key(account: &AccountHash) -> String {
+    format!("request-{}", account)
+}
+
+fn approvals_key(account: &AccountHash) -> String {
+    format!("uref-approvals-{}", account)
 }
 
 /// Getter function from context storage.
 /// Returns the previously data previously stored under `name` key,
 /// or returns the default value of the type expected at the end of the call.
 fn get_key<T: FromBytes + CLTyped + Default>(name: &str) -> T {
-    match runtime::get_key(name) {
-        None => Default::default(),
-        Some(value) => {
-            let key = value.try_into().unwrap_or_revert();
-            storage::read(key).unwrap_or_revert().unwrap_or_revert()
-        }
-    }
+    get_optional_key(name).unwrap_or_default()
+}
+
+/// Same as `get_key`, but tells apart a missing key from a stored default value.
+fn get_optional_key<T: FromBytes + CLTyped>(name: &str) -> Option<T> {
+    runtime::get_key(name).map(|value| {
+        let key = value.try_into().unwrap_or_revert();
+        storage::read(key).unwrap_or_revert().unwrap_or_revert()
+    })
 }
 
 /// Creates new storage key `name` and stores `value` to it.
@@ -206,5 +457,5 @@ fn set_key<T: ToBytes + CLTyped>(name: &str, value: T) {
 
 #[no_mangle]
 fn call() {
-    install_or_upgrade_contract(String::from("locked-with-share"));
+    install_or_upgrade_contract(String::from(keys::LOCKED_WITH_SHARE_CONTRACT));
 }
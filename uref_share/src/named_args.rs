@@ -0,0 +1,51 @@
+//! Reading named arguments, reverting with the code `arg_errors` gives the argument.
+//!
+//! Every contract and session binary declares this module. It calls the `contract` API, which the
+//! `uref_share_client` crate they all depend on is kept free of.
+use contract::{contract_api::runtime::revert, ext_ffi, unwrap_or_revert::UnwrapOrRevert};
+use types::{
+    api_error,
+    bytesrepr::{self, FromBytes},
+    ApiError, CLTyped,
+};
+use uref_share_client::arg_errors::{self, ArgError};
+
+/// Returns the named argument `name`, reverting with its own error code if the caller
+/// did not supply it or it does not deserialize as `T`.
+pub fn get_named_arg<T: FromBytes + CLTyped>(name: &str) -> T {
+    get_optional_named_arg(name)
+        .unwrap_or_revert_with(arg_errors::api_error(name, ArgError::Missing))
+}
+
+/// Returns the named argument `name` if the caller supplied it, reverting with its own error code
+/// if it does not deserialize as `T`.
+pub fn get_optional_named_arg<T: FromBytes + CLTyped>(name: &str) -> Option<T> {
+    let mut arg_size: usize = 0;
+    let ret = unsafe {
+        ext_ffi::casper_get_named_arg_size(
+            name.as_bytes().as_ptr(),
+            name.len(),
+            &mut arg_size as *mut usize,
+        )
+    };
+    match api_error::result_from(ret) {
+        Ok(_) => {}
+        Err(ApiError::MissingArgument) => return None,
+        Err(e) => revert(e),
+    }
+    let mut bytes = vec![0u8; arg_size];
+    if arg_size > 0 {
+        let ret = unsafe {
+            ext_ffi::casper_get_named_arg(
+                name.as_bytes().as_ptr(),
+                name.len(),
+                bytes.as_mut_ptr(),
+                arg_size,
+            )
+        };
+        api_error::result_from(ret).unwrap_or_revert();
+    }
+    let value = bytesrepr::deserialize(bytes)
+        .unwrap_or_revert_with(arg_errors::api_error(name, ArgError::Invalid));
+    Some(value)
+}
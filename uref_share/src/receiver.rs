@@ -0,0 +1,137 @@
+#![no_main]
+extern crate alloc;
+
+mod named_args;
+
+use std::convert::TryInto;
+
+use contract::{
+    contract_api::{runtime, runtime::revert, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    bytesrepr::FromBytes,
+    contracts::{ContractPackageHash, NamedKeys},
+    runtime_args, ApiError, CLTyped, Key, RuntimeArgs, URef,
+};
+use uref_share_client::{args, decode::Push, entry_points, keys, schema};
+
+use named_args::get_named_arg;
+
+/// Deployer/upgrader function. Tries to retrieve any data presumably stored earlier
+/// in the context associated to to `name`. If there is data, proceeds with that,
+/// otherwise creates a new contract.
+pub fn install_or_upgrade_contract(name: String) {
+    let contract_package_hash: ContractPackageHash =
+        match runtime::get_key(&keys::package_hash(&name)) {
+            Some(contract_package_hash) => {
+                contract_package_hash.into_hash().unwrap_or_revert().into()
+            }
+            None => {
+                let (contract_package_hash, access_token) =
+                    storage::create_contract_package_at_hash();
+                runtime::put_key(&keys::package_hash(&name), contract_package_hash.into());
+                // Store package hash wrapped so we can use it in the test context
+                runtime::put_key(
+                    &keys::wrapped_package_hash(&name),
+                    storage::new_uref(contract_package_hash).into(),
+                );
+                runtime::put_key(&keys::access_uref(&name), access_token.into());
+                contract_package_hash
+            }
+        };
+    // Group allowed to make the contract use its URefs, the installer is its first member.
+    if runtime::get_key(&keys::operator_access(&name)).is_none() {
+        let operator_uref = storage::create_contract_user_group(
+            contract_package_hash,
+            "operator",
+            1,
+            alloc::collections::BTreeSet::default(),
+        )
+        .unwrap_or_revert()
+        .pop()
+        .unwrap_or_revert();
+        runtime::put_key(&keys::operator_access(&name), operator_uref.into());
+    }
+    // The sharing contract URefs are accepted from.
+    let share_contract: ContractPackageHash = get_named_arg(args::SHARE_HASH);
+
+    let mut named_keys = NamedKeys::new();
+    named_keys.insert(
+        "share_contract".to_string(),
+        storage::new_uref(share_contract).into(),
+    );
+    named_keys.insert(
+        "package_hash".to_string(),
+        storage::new_uref(contract_package_hash).into(),
+    );
+
+    let entry_points = schema::receiver();
+    let (contract_hash, _) =
+        storage::add_contract_version(contract_package_hash, entry_points, named_keys);
+
+    runtime::put_key(&name, contract_hash.into());
+    runtime::put_key(
+        &keys::wrapped(&name),
+        storage::new_uref(contract_hash).into(),
+    );
+}
+
+// Entry points
+
+/// Callback the URef sharing contract pushes URefs to, when a contract is the recipient.
+/// Any contract implementing an entry point with this name and these arguments can receive URefs.
+/// The URef is stored in the contracts named keys under the same name `claim.wasm` would use.
+/// The engine does not tell which contract called, so the sharing contract the receiver was installed
+/// with is asked whether it is pushing this very entry to this contract. Reverts with `300` otherwise.
+#[no_mangle]
+fn receive_uref() {
+    let uref: URef = get_named_arg(args::UREF);
+    let label: String = get_named_arg(args::LABEL);
+    let source_package: Option<ContractPackageHash> = get_named_arg(args::SOURCE_PACKAGE);
+    let pushed: Option<Push> = runtime::call_versioned_contract(
+        get_key("share_contract"),
+        None,
+        entry_points::PUSHED_ENTRY,
+        runtime_args! {},
+    );
+    if pushed
+        != Some((
+            get_key("package_hash"),
+            (source_package, label.clone(), uref),
+        ))
+    {
+        revert(ApiError::User(300));
+    }
+    runtime::put_key(&keys::claimed_uref(source_package, &label), Key::URef(uref));
+}
+
+/// Calls the access restricted function of the locked contract with the URefs held by this contract.
+/// Only callable with an `operator` group URef.
+#[no_mangle]
+fn call_locked() {
+    let locked_package: ContractPackageHash = get_named_arg(args::LOCKED_PACKAGE);
+    let _: () = runtime::call_versioned_contract(
+        locked_package,
+        None,
+        entry_points::GROUP_ACCESS_ONLY,
+        runtime_args! {},
+    );
+}
+
+// Utility functions
+
+/// Getter function from context storage.
+/// Returns the data previously stored under `name` key, reverting if there is none.
+fn get_key<T: FromBytes + CLTyped>(name: &str) -> T {
+    let key = runtime::get_key(name)
+        .unwrap_or_revert()
+        .try_into()
+        .unwrap_or_revert();
+    storage::read(key).unwrap_or_revert().unwrap_or_revert()
+}
+
+#[no_mangle]
+fn call() {
+    install_or_upgrade_contract(String::from(keys::RECEIVER_CONTRACT));
+}
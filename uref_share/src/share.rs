@@ -1,106 +1,1103 @@
 #![no_main]
 extern crate alloc;
 
+mod named_args;
+
 use std::convert::TryInto;
 
 use contract::{
-    contract_api::{runtime, runtime::get_named_arg, storage},
+    contract_api::{runtime, runtime::revert, storage, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
 use types::{
+    account::AccountHash,
     bytesrepr::{FromBytes, ToBytes},
-    contracts::ContractPackageHash,
-    CLType, CLTyped, CLValue, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Parameter,
-    PublicKey, URef,
+    contracts::{ContractPackageHash, NamedKeys},
+    runtime_args, ApiError, CLTyped, CLValue, Key, PublicKey, RuntimeArgs, URef, U512,
+};
+use uref_share_client::{
+    args, decode::Push, entry_points, keys, schema, Entry, DEFAULT_LABEL, SHARE_INTERFACE_VERSION,
 };
 
-/// Returns the list of the entry points in the contract with added group security.
-pub fn get_entry_points() -> EntryPoints {
-    let mut entry_points = EntryPoints::new();
-    entry_points.add_entry_point(EntryPoint::new(
-        "retrieve_uref",
-        vec![],
-        CLType::URef,
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-    entry_points.add_entry_point(EntryPoint::new(
-        "store_uref",
-        vec![
-            Parameter::new("uref".to_string(), CLType::URef),
-            Parameter::new("account_pubkey".to_string(), CLType::PublicKey),
-        ],
-        CLType::Unit,
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-    entry_points
-}
+use named_args::{get_named_arg, get_optional_named_arg};
+
+/// Delegation depth used when the installer does not provide `max_delegation_depth`.
+const DEFAULT_MAX_DELEGATION_DEPTH: u8 = 1;
+/// Deposit fee paid for an entry and the block time it expires at, `0` if it never does.
+type Fee = (U512, u64);
+/// A stored URef waiting to be claimed, together with the account that deposited it and the fee it paid.
+type Pending = (Entry, AccountHash, Fee);
+/// An entry delegated to an account, by its source package and label, with who delegated it,
+/// at which depth and the URef the source package issued for the account,
+/// as `(source, label, (delegator, depth, uref))`.
+type Delegation = (ContractPackageHash, String, (AccountHash, u8, URef));
+/// The approvers of an entry pending for a recipient, by its source package and label.
+type Approval = (Option<ContractPackageHash>, String, Vec<AccountHash>);
 
 /// Deployer/upgrader function. Tries to retrieve any data presumably stored earlier
 /// in the context associated to to `name`. If there is data, proceeds with that,
 /// otherwise creates a new contract.
 pub fn install_or_upgrade_contract(name: String) {
     let contract_package_hash: ContractPackageHash =
-        match runtime::get_key(&format!("{}-package-hash", name)) {
+        match runtime::get_key(&keys::package_hash(&name)) {
             Some(contract_package_hash) => {
                 contract_package_hash.into_hash().unwrap_or_revert().into()
             }
             None => {
                 let (contract_package_hash, access_token) =
                     storage::create_contract_package_at_hash();
-                runtime::put_key(
-                    &format!("{}-package-hash", name),
-                    contract_package_hash.into(),
-                );
+                runtime::put_key(&keys::package_hash(&name), contract_package_hash.into());
                 // Store package hash wrapped so we can use it in the test context
                 runtime::put_key(
-                    &format!("{}-wrapped-package-hash", name),
+                    &keys::wrapped_package_hash(&name),
                     storage::new_uref(contract_package_hash).into(),
                 );
-                runtime::put_key(&format!("{}-access-uref", name), access_token.into());
+                runtime::put_key(&keys::access_uref(&name), access_token.into());
                 contract_package_hash
             }
         };
-    let entry_points = get_entry_points();
+    // Group guarding the administrative entry points, the installer is its first member.
+    // Packages installed by the first release are upgraded without one.
+    if runtime::get_key(&keys::owner_access(&name)).is_none() {
+        let owner_uref = storage::create_contract_user_group(
+            contract_package_hash,
+            "owner",
+            1,
+            alloc::collections::BTreeSet::default(),
+        )
+        .unwrap_or_revert()
+        .pop()
+        .unwrap_or_revert();
+        runtime::put_key(&keys::owner_access(&name), owner_uref.into());
+    }
+    let max_delegation_depth: u8 =
+        get_optional_named_arg(args::MAX_DELEGATION_DEPTH).unwrap_or(DEFAULT_MAX_DELEGATION_DEPTH);
+    // Release policy, entries need `approval_threshold` distinct approvals before they can be retrieved,
+    // from the `approvers` or, with an `approver_package`, from holders of its group URefs.
+    let approvers: Vec<AccountHash> = get_optional_named_arg::<Vec<PublicKey>>(args::APPROVERS)
+        .unwrap_or_default()
+        .iter()
+        .map(PublicKey::to_account_hash)
+        .collect();
+    let approval_threshold: u8 =
+        get_optional_named_arg(args::APPROVAL_THRESHOLD).unwrap_or_default();
+    let approver_package: Option<ContractPackageHash> =
+        get_optional_named_arg(args::APPROVER_PACKAGE);
+    if approver_package.is_none() && approval_threshold as usize > approvers.len() {
+        revert(ApiError::User(107));
+    }
+
+    let mut named_keys = NamedKeys::new();
+    named_keys.insert(
+        "max_delegation_depth".to_string(),
+        storage::new_uref(max_delegation_depth).into(),
+    );
+    named_keys.insert("approvers".to_string(), storage::new_uref(approvers).into());
+    named_keys.insert(
+        "approval_threshold".to_string(),
+        storage::new_uref(approval_threshold).into(),
+    );
+    named_keys.insert(
+        "approver_package".to_string(),
+        storage::new_uref(approver_package).into(),
+    );
+    // Administrative state, the installer owns the contract.
+    named_keys.insert(
+        "owner".to_string(),
+        storage::new_uref(runtime::get_caller()).into(),
+    );
+    named_keys.insert(
+        "owner_uref".to_string(),
+        storage::new_uref(
+            runtime::get_key(&keys::owner_access(&name))
+                .and_then(|key| key.into_uref())
+                .unwrap_or_revert(),
+        )
+        .into(),
+    );
+    named_keys.insert(
+        "pending_owner".to_string(),
+        storage::new_uref(None::<(AccountHash, URef)>).into(),
+    );
+    named_keys.insert("paused".to_string(), storage::new_uref(false).into());
+    // Set while an entry is pushed to a contract recipient, see `pushed_entry`.
+    named_keys.insert(
+        "pushing".to_string(),
+        storage::new_uref(None::<Push>).into(),
+    );
+    named_keys.insert(
+        "package_hash".to_string(),
+        storage::new_uref(contract_package_hash).into(),
+    );
+    // Quotas, `0` stands for no limit.
+    for quota in args::QUOTAS.iter() {
+        let limit: u32 = get_optional_named_arg(quota).unwrap_or_default();
+        named_keys.insert(quota.to_string(), storage::new_uref(limit).into());
+    }
+    named_keys.insert("total_pending".to_string(), storage::new_uref(0u32).into());
+    // Deposit fees, held in the fee purse until the entry is claimed, reclaimed or expires.
+    let fee: U512 = get_optional_named_arg(args::FEE).unwrap_or_default();
+    let fee_expiry: u64 = get_optional_named_arg(args::FEE_EXPIRY).unwrap_or_default();
+    named_keys.insert("fee".to_string(), storage::new_uref(fee).into());
+    named_keys.insert(
+        "fee_expiry".to_string(),
+        storage::new_uref(fee_expiry).into(),
+    );
+    // The fee purse carries over to new versions with the named keys, so it is only created once.
+    if runtime::get_key(&keys::wrapped_fee_purse_address(&name)).is_none() {
+        let fee_purse = system::create_purse();
+        named_keys.insert("fee_purse".to_string(), fee_purse.into());
+        // Store the fee purse address wrapped so we can check its balance in the test context
+        runtime::put_key(
+            &keys::wrapped_fee_purse_address(&name),
+            storage::new_uref(fee_purse.addr()).into(),
+        );
+    }
+
+    let entry_points = schema::share();
     let (contract_hash, _) =
-        storage::add_contract_version(contract_package_hash, entry_points, Default::default());
+        storage::add_contract_version(contract_package_hash, entry_points, named_keys);
 
     runtime::put_key(&name, contract_hash.into());
     runtime::put_key(
-        &format!("{}-wrapped", name),
+        &keys::wrapped(&name),
         storage::new_uref(contract_hash).into(),
     );
 }
 
 // Entry points
 
+/// Returns the most recently stored URef pending for the caller that gathered the approvals
+/// required, without claiming it. Reverts with `106` if none of the pending ones did.
 #[no_mangle]
 fn retrieve_uref() {
-    let uref: URef = get_key(&runtime::get_caller().to_string());
+    ensure_not_paused();
+    let caller = Key::Account(runtime::get_caller());
+    let is_released = released(&caller);
+    let pending = pending_entries(&caller);
+    let uref: URef = match pending
+        .iter()
+        .rev()
+        .find(|(entry, _, _)| is_released(entry))
+    {
+        Some(((_, _, uref), _, _)) => *uref,
+        None if pending.is_empty() => URef::default(),
+        None => revert(ApiError::User(106)),
+    };
     runtime::ret(CLValue::from_t(uref).unwrap_or_revert())
 }
 
+/// Returns and removes every entry pending for the caller that matches the optional
+/// `source_package` and `label` filters. Entries still waiting for approvals stay pending,
+/// reverting with `106` if that leaves none to claim.
+#[no_mangle]
+fn claim_urefs() {
+    let source_package: Option<ContractPackageHash> = get_named_arg(args::SOURCE_PACKAGE);
+    let label: Option<String> = get_named_arg(args::LABEL);
+    // Ownership handovers go through the vault as well, and have to work while paused.
+    // Only this contract deposits entries tagged with its own package, for the pending owner.
+    let caller = runtime::get_caller();
+    let handover = source_package == Some(get_key("package_hash"))
+        && get_key::<Option<(AccountHash, URef)>>("pending_owner")
+            .map_or(false, |(new_owner, _)| new_owner == caller);
+    if !handover {
+        ensure_not_paused();
+    }
+    let caller = Key::Account(caller);
+
+    let matches = |(source, entry_label, _): &Entry| {
+        source_package.map_or(true, |filter| *source == Some(filter))
+            && label.as_ref().map_or(true, |filter| entry_label == filter)
+    };
+    let is_released = released(&caller);
+    let claimed: Vec<Entry> = claim_matching(&caller, |entry| matches(entry) && is_released(entry));
+    if claimed.is_empty()
+        && pending_entries(&caller)
+            .iter()
+            .any(|(entry, _, _)| matches(entry))
+    {
+        revert(ApiError::User(106));
+    }
+    runtime::ret(CLValue::from_t(claimed).unwrap_or_revert())
+}
+
+/// Returns and removes every entry of the caller stored as part of `bundle`, all of them or none.
+/// Reverts with `117` if there is no such bundle. Entries leave their bundle along with the pending
+/// entries, so only a bundle stored by an earlier version can revert with `118`, for entries
+/// that are no longer pending. Reverts with `106` if some of its entries still wait for approvals.
+#[no_mangle]
+fn claim_bundle() {
+    ensure_not_paused();
+    let bundle: String = get_named_arg(args::BUNDLE);
+    let caller = Key::Account(runtime::get_caller());
+    let is_released = released(&caller);
+
+    let members: Vec<(Option<ContractPackageHash>, String)> =
+        get_key(&bundle_key(&caller, &bundle));
+    if members.is_empty() {
+        revert(ApiError::User(117));
+    }
+    let claimed: Vec<Entry> = claim_matching(&caller, |(source, label, _)| {
+        members.contains(&(*source, label.clone()))
+    });
+    if claimed.len() != members.len() {
+        revert(ApiError::User(118));
+    }
+    if !claimed.iter().all(&is_released) {
+        revert(ApiError::User(106));
+    }
+    runtime::remove_key(&bundle_key(&caller, &bundle));
+    runtime::ret(CLValue::from_t(claimed).unwrap_or_revert())
+}
+
+/// Stores a URef for the recipient under `label`, defaulting to `admin`,
+/// optionally tagged with the `source_package` it grants access to, see `ensure_from_source`.
+/// An entry already pending with the same source and label is overwritten.
+/// Contract recipients get the URef pushed as soon as it is released, see `store_uref_for_contract`.
+/// Entries for accounts can be grouped into a `bundle`, claimed at once with `claim_bundle`,
+/// see `add_to_bundle`. Reverts with `123` if a `bundle` is given for a contract recipient.
+/// Entries pay the deposit `fee`, if one is set, from `purse`.
 #[no_mangle]
 fn store_uref() {
-    let user: PublicKey = get_named_arg("account_pubkey");
-    let uref: URef = get_named_arg("uref");
-    set_key(&user.to_account_hash().to_string(), uref);
+    ensure_not_paused();
+    let uref: URef = get_named_arg(args::UREF);
+    let label: String =
+        get_optional_named_arg(args::LABEL).unwrap_or_else(|| DEFAULT_LABEL.to_string());
+    let source_package: Option<ContractPackageHash> = get_optional_named_arg(args::SOURCE_PACKAGE);
+    ensure_from_source(source_package, uref);
+    let recipient = get_recipient();
+    let bundle: Option<String> = get_optional_named_arg(args::BUNDLE);
+    if let (Recipient::Contract(_), Some(_)) = (&recipient, &bundle) {
+        revert(ApiError::User(123));
+    }
+    let fee = collect_fee();
+    deposit(&recipient.key(), (source_package, label.clone(), uref), fee);
+    if let Some(bundle) = bundle {
+        add_to_bundle(&recipient.key(), &bundle, (source_package, label));
+    }
+    if let Recipient::Contract(contract_package) = recipient {
+        push_released(contract_package);
+    }
+}
+
+/// Contracts can not pick URefs up themselves, so instead they get the URef pushed
+/// to their `receive_uref` entry point, see `receiver.rs` for the callback convention.
+/// The entry goes through the same quotas, fee and release policy as one for an account,
+/// and is pushed once it gathered the approvals required, right away if there are none.
+#[no_mangle]
+fn store_uref_for_contract() {
+    ensure_not_paused();
+    let contract_package: ContractPackageHash = get_named_arg(args::CONTRACT_PACKAGE);
+    let uref: URef = get_named_arg(args::UREF);
+    let label: String =
+        get_optional_named_arg(args::LABEL).unwrap_or_else(|| DEFAULT_LABEL.to_string());
+    let source_package: Option<ContractPackageHash> = get_optional_named_arg(args::SOURCE_PACKAGE);
+    ensure_from_source(source_package, uref);
+    let fee = collect_fee();
+    deposit(
+        &Recipient::Contract(contract_package).key(),
+        (source_package, label, uref),
+        fee,
+    );
+    push_released(contract_package);
+}
+
+/// Records the callers approval to release each entry pending for the given recipient.
+/// Approvals count per entry, an entry deposited later needs approvals of its own.
+/// See `ensure_approver` for who may approve.
+/// Entries for a contract are pushed to it once they gathered enough approvals.
+#[no_mangle]
+fn approve_release() {
+    let recipient = get_recipient();
+    let approver = runtime::get_caller();
+    ensure_approver(&approver);
+
+    let key = recipient.key();
+    let mut approvals: Vec<Approval> = get_key(&approvals_key(&key));
+    for ((source, label, _), _, _) in pending_entries(&key) {
+        match approvals
+            .iter_mut()
+            .find(|(approved_source, approved_label, _)| {
+                *approved_source == source && *approved_label == label
+            }) {
+            Some((_, _, approvers)) if approvers.contains(&approver) => {}
+            Some((_, _, approvers)) => approvers.push(approver),
+            None => approvals.push((source, label, vec![approver])),
+        }
+    }
+    set_key(&approvals_key(&key), approvals);
+    if let Recipient::Contract(contract_package) = recipient {
+        push_released(contract_package);
+    }
+}
+
+/// Lets a holder pass an entry it received through this contract on to another account.
+/// The source package of the entry issues a URef of its own for the delegate, so that revoking
+/// the delegation can take it back without touching the access of the delegator.
+/// The resulting chain may not grow past `max_delegation_depth`, nor lead back to an account on it.
+/// The delegator pays the deposit fee, the same as for `store_uref`.
+#[no_mangle]
+fn delegate() {
+    ensure_not_paused();
+    let uref: URef = get_named_arg(args::UREF);
+    let recipient = get_account_recipient();
+    let delegator = runtime::get_caller();
+
+    let (source, label, _) = match held_entries(&delegator)
+        .into_iter()
+        .find(|(_, _, held)| held.addr() == uref.addr())
+    {
+        Some(entry) => entry,
+        None => revert(ApiError::User(100)),
+    };
+    // Untagged entries have no package to issue a URef, and the ones tagged with this contract
+    // are ownership handovers.
+    let source = match source {
+        Some(source) if source != get_key::<ContractPackageHash>("package_hash") => source,
+        _ => revert(ApiError::User(120)),
+    };
+    let depth = delegation_depth(&delegator, source, &label) + 1;
+    if depth > get_key::<u8>("max_delegation_depth") {
+        revert(ApiError::User(101));
+    }
+    if find_delegation(&recipient, source, &label).is_some()
+        || held_entries(&recipient)
+            .iter()
+            .any(|(held_source, held_label, _)| {
+                *held_source == Some(source) && *held_label == label
+            })
+    {
+        revert(ApiError::User(102));
+    }
+    if delegation_ancestors(&delegator, source, &label).contains(&recipient) {
+        revert(ApiError::User(121));
+    }
+
+    let fee = collect_fee();
+    let issued: URef = runtime::call_versioned_contract(
+        source,
+        None,
+        entry_points::ISSUE_DELEGATE_UREF,
+        runtime_args! {args::UREF => uref, args::ACCOUNT => recipient},
+    );
+    deposit(
+        &Key::Account(recipient),
+        (Some(source), label.clone(), issued),
+        fee,
+    );
+    let mut delegations: Vec<Delegation> = get_key(&delegations_key(&recipient));
+    delegations.push((source, label, (delegator, depth, issued)));
+    set_key(&delegations_key(&recipient), delegations);
+    let mut delegates: Vec<AccountHash> = get_key(&delegates_key(&delegator));
+    if !delegates.contains(&recipient) {
+        delegates.push(recipient);
+        set_key(&delegates_key(&delegator), delegates);
+    }
+}
+
+/// Withdraws every delegation made to the target by the caller, or by anyone the caller delegated
+/// the same entry to, together with every delegation made further down those chains.
+/// The source packages take the URefs they issued for the delegations back.
+#[no_mangle]
+fn revoke_delegation() {
+    let target = get_account_recipient();
+    let caller = runtime::get_caller();
+
+    let delegations: Vec<Delegation> = get_key(&delegations_key(&target));
+    if delegations.is_empty() {
+        revert(ApiError::User(103));
+    }
+    let revoked: Vec<(ContractPackageHash, String)> = delegations
+        .into_iter()
+        .map(|(source, label, _)| (source, label))
+        .filter(|(source, label)| delegation_ancestors(&target, *source, label).contains(&caller))
+        .collect();
+    if revoked.is_empty() {
+        revert(ApiError::User(104));
+    }
+    for (source, label) in revoked {
+        remove_delegation(&target, source, &label);
+    }
+}
+
+/// Stops all deposits and claims until `unpause` is called.
+#[no_mangle]
+fn pause() {
+    set_key("paused", true);
+}
+
+/// Lifts a `pause`.
+#[no_mangle]
+fn unpause() {
+    set_key("paused", false);
+}
+
+/// First step of an ownership handover. Stores a fresh `owner` group URef and the package
+/// access URef, which the current owner passes in, for `new_owner` to claim from this contract.
+/// The current owner stays in charge until the handover is accepted.
+/// A handover still pending is superseded: its `owner` group URef is removed from the group
+/// and its entries are taken back, unless they were claimed already.
+/// The handover entries are exempt from the deposit fee, the owner would only pay its own contract.
+#[no_mangle]
+fn transfer_ownership() {
+    let new_owner: AccountHash = get_named_arg(args::NEW_OWNER);
+    let package_access: URef = get_named_arg(args::PACKAGE_ACCESS);
+    let contract_package_hash: ContractPackageHash = get_key("package_hash");
+
+    if let Some((superseded, superseded_uref)) =
+        get_key::<Option<(AccountHash, URef)>>("pending_owner")
+    {
+        let mut removed = alloc::collections::BTreeSet::new();
+        removed.insert(superseded_uref);
+        storage::remove_contract_user_group_urefs(contract_package_hash, "owner", removed)
+            .unwrap_or_revert();
+        let recipient = Key::Account(superseded);
+        let (handover, pending): (Vec<Pending>, Vec<Pending>) = pending_entries(&recipient)
+            .into_iter()
+            .partition(|((source, _, _), _, _)| *source == Some(contract_package_hash));
+        set_pending(&recipient, pending);
+        handover.iter().for_each(settle);
+    }
+
+    let owner_uref = storage::provision_contract_user_group_uref(contract_package_hash, "owner")
+        .unwrap_or_revert();
+    let source = Some(contract_package_hash);
+    deposit(
+        &Key::Account(new_owner),
+        (source, "owner".to_string(), owner_uref),
+        U512::zero(),
+    );
+    deposit(
+        &Key::Account(new_owner),
+        (source, keys::PACKAGE_ACCESS.to_string(), package_access),
+        U512::zero(),
+    );
+    set_key("pending_owner", Some((new_owner, owner_uref)));
+}
+
+/// Replaces the quotas, `0` stands for no limit.
+/// Entries already pending are kept even if they go over the new quotas.
+#[no_mangle]
+fn set_quotas() {
+    for quota in args::QUOTAS.iter() {
+        let limit: u32 = get_named_arg(quota);
+        set_key(quota, limit);
+    }
+}
+
+/// Returns `(pending entries deposited by, pending entries for, all pending entries)`
+/// for the given account.
+#[no_mangle]
+fn get_usage() {
+    let account = get_account_recipient();
+    let usage: (u32, u32, u32) = (
+        get_key(&pending_count_key(&account)),
+        pending_entries(&Key::Account(account)).len() as u32,
+        get_key("total_pending"),
+    );
+    runtime::ret(CLValue::from_t(usage).unwrap_or_revert())
+}
+
+/// Sets the deposit `fee` charged by `store_uref` and `delegate`, and after how many milliseconds
+/// an entry that paid it expires, `0` for never.
+#[no_mangle]
+fn set_fee() {
+    let fee: U512 = get_named_arg(args::FEE);
+    let fee_expiry: u64 = get_named_arg(args::FEE_EXPIRY);
+    set_key("fee", fee);
+    set_key("fee_expiry", fee_expiry);
+}
+
+/// Takes back an entry the caller stored for the recipient under `label`, defaulting to `admin`,
+/// and `source_package`, refunding its fee. Reverts with `116` if there is no such entry.
+#[no_mangle]
+fn reclaim_uref() {
+    let recipient = get_recipient().key();
+    let label: String =
+        get_optional_named_arg(args::LABEL).unwrap_or_else(|| DEFAULT_LABEL.to_string());
+    let source_package: Option<ContractPackageHash> = get_optional_named_arg(args::SOURCE_PACKAGE);
+    let caller = runtime::get_caller();
+
+    let (reclaimed, pending): (Vec<Pending>, Vec<Pending>) = pending_entries(&recipient)
+        .into_iter()
+        .partition(|((source, entry_label, _), depositor, _)| {
+            *source == source_package && *entry_label == label && *depositor == caller
+        });
+    if reclaimed.is_empty() {
+        revert(ApiError::User(116));
+    }
+    set_pending(&recipient, pending);
+    reclaimed.iter().for_each(settle);
+}
+
+/// Removes the expired entries pending for the given recipient, forfeiting their fees to the owner.
+#[no_mangle]
+fn expire_entries() {
+    let recipient = get_recipient().key();
+    let (expired, pending): (Vec<Pending>, Vec<Pending>) = pending_entries(&recipient)
+        .into_iter()
+        .partition(is_expired);
+    set_pending(&recipient, pending);
+    expired.iter().for_each(settle);
+}
+
+/// Second step of an ownership handover, callable only by the account named in `transfer_ownership`,
+/// once it claimed the package access URef it passes back in.
+/// Removes the `owner` group URef of the previous owner.
+#[no_mangle]
+fn accept_ownership() {
+    let caller = runtime::get_caller();
+    let (new_owner, owner_uref) = match get_key::<Option<(AccountHash, URef)>>("pending_owner") {
+        Some(pending) if pending.0 == caller => pending,
+        _ => revert(ApiError::User(110)),
+    };
+    // Managing groups needs the package access URef in the context of the call.
+    let _: URef = get_named_arg(args::PACKAGE_ACCESS);
+    let contract_package_hash: ContractPackageHash = get_key("package_hash");
+
+    let mut previous = alloc::collections::BTreeSet::new();
+    previous.insert(get_key::<URef>("owner_uref"));
+    storage::remove_contract_user_group_urefs(contract_package_hash, "owner", previous)
+        .unwrap_or_revert();
+
+    set_key("owner", new_owner);
+    set_key("owner_uref", owner_uref);
+    set_key("pending_owner", None::<(AccountHash, URef)>);
+}
+
+/// Returns the entry this contract is pushing, as `(recipient package, entry)`, while the
+/// `receive_uref` entry point of the recipient runs, and `None` otherwise.
+/// Recipients check it to make sure a URef they are handed comes from this contract.
+#[no_mangle]
+fn pushed_entry() {
+    let pushed: Option<Push> = get_key("pushing");
+    runtime::ret(CLValue::from_t(pushed).unwrap_or_revert())
+}
+
+/// Returns the interface this contract implements, `SHARE_INTERFACE_VERSION`.
+/// Contracts calling it check it first, as upgrades install new versions under the same package.
+#[no_mangle]
+fn version() {
+    let version: u32 = SHARE_INTERFACE_VERSION;
+    runtime::ret(CLValue::from_t(version).unwrap_or_revert())
 }
 
 // Utility functions
 
+/// Reverts with `111` while the contract is paused.
+fn ensure_not_paused() {
+    if get_key::<bool>("paused") {
+        revert(ApiError::User(111));
+    }
+}
+
+/// Canonical form of a recipient, whichever argument it was supplied with.
+enum Recipient {
+    Account(AccountHash),
+    Contract(ContractPackageHash),
+}
+
+impl Recipient {
+    /// The recipient as a `Key`, which the keys stored per recipient are named after.
+    fn key(&self) -> Key {
+        match self {
+            Recipient::Account(account_hash) => Key::Account(*account_hash),
+            Recipient::Contract(contract_package) => Key::Hash(contract_package.value()),
+        }
+    }
+}
+
+/// Reads the recipient from `account_pubkey`, `account_hash` or `recipient`, in that order.
+/// Public keys of any algorithm and `Key::Account` normalize to the account hash,
+/// `Key::Hash` to a contract package hash. Any other `Key` variant reverts with `108`.
+fn get_recipient() -> Recipient {
+    if let Some(public_key) = get_optional_named_arg::<PublicKey>(args::ACCOUNT_PUBKEY) {
+        return Recipient::Account(public_key.to_account_hash());
+    }
+    if let Some(account_hash) = get_optional_named_arg::<AccountHash>(args::ACCOUNT_HASH) {
+        return Recipient::Account(account_hash);
+    }
+    match get_named_arg::<Key>(args::RECIPIENT) {
+        Key::Account(account_hash) => Recipient::Account(account_hash),
+        Key::Hash(addr) => Recipient::Contract(addr.into()),
+        _ => revert(ApiError::User(108)),
+    }
+}
+
+/// Same as `get_recipient`, for entry points only accounts can be the subject of.
+/// Reverts with `108` for contract recipients.
+fn get_account_recipient() -> AccountHash {
+    match get_recipient() {
+        Recipient::Account(account_hash) => account_hash,
+        Recipient::Contract(_) => revert(ApiError::User(108)),
+    }
+}
+
+/// Reverts unless `uref` grants access to `source_package`, if one is given.
+/// The engine can not tell which contract called, nor which groups a URef belongs to, so the package
+/// is asked instead: its `confirm_deposit` entry point is restricted to the groups whose URefs it
+/// hands out, and calling it with `uref` only passes the group check if `uref` belongs to one of them.
+/// Entries tagged with this contract itself are reserved for ownership handovers and revert with `119`.
+fn ensure_from_source(source_package: Option<ContractPackageHash>, uref: URef) {
+    let source_package = match source_package {
+        Some(source_package) => source_package,
+        None => return,
+    };
+    if source_package == get_key::<ContractPackageHash>("package_hash") {
+        revert(ApiError::User(119));
+    }
+    let _: () = runtime::call_versioned_contract(
+        source_package,
+        None,
+        entry_points::CONFIRM_DEPOSIT,
+        runtime_args! {args::UREF => uref},
+    );
+}
+
+/// Claims the entries pending for `contract_package` that gathered the approvals the release policy
+/// requires and pushes them to it.
+fn push_released(contract_package: ContractPackageHash) {
+    let recipient = Recipient::Contract(contract_package).key();
+    let is_released = released(&recipient);
+    for entry in claim_matching(&recipient, is_released) {
+        push_to_contract(contract_package, entry);
+    }
+}
+
+/// Hands `entry` to the `receive_uref` entry point of `contract_package`,
+/// which can confirm it with `pushed_entry` while the call lasts.
+fn push_to_contract(contract_package: ContractPackageHash, entry: Entry) {
+    set_key("pushing", Some((contract_package, entry.clone())));
+    let (source_package, label, uref) = entry;
+    let _: () = runtime::call_versioned_contract(
+        contract_package,
+        None,
+        entry_points::RECEIVE_UREF,
+        runtime_args! {args::UREF => uref, args::LABEL => label, args::SOURCE_PACKAGE => source_package},
+    );
+    set_key("pushing", None::<Push>);
+}
+
+/// Adds `entry`, deposited by the caller for `fee`, to the entries pending for `recipient`,
+/// replacing a pending entry with the same source and label along with the approvals it gathered.
+/// Reverts if the deposit goes over one of the quotas.
+fn deposit(recipient: &Key, entry: Entry, fee: U512) {
+    let depositor = runtime::get_caller();
+    let (replaced, mut pending): (Vec<Pending>, Vec<Pending>) = pending_entries(recipient)
+        .into_iter()
+        .partition(|((source, label, _), _, _)| *source == entry.0 && *label == entry.1);
+    replaced.iter().for_each(settle);
+
+    let depositor_pending: u32 = get_key(&pending_count_key(&depositor));
+    let total_pending: u32 = get_key("total_pending");
+    ensure_within_quota(
+        depositor_pending,
+        args::MAX_PENDING_PER_DEPOSITOR,
+        ApiError::User(112),
+    );
+    ensure_within_quota(
+        pending.len() as u32,
+        args::MAX_ENTRIES_PER_RECIPIENT,
+        ApiError::User(113),
+    );
+    ensure_within_quota(total_pending, args::MAX_TOTAL_ENTRIES, ApiError::User(114));
+    set_key(&pending_count_key(&depositor), depositor_pending + 1);
+    set_key("total_pending", total_pending + 1);
+
+    let fee_expiry: u64 = get_key("fee_expiry");
+    let expires_at = if fee.is_zero() || fee_expiry == 0 {
+        0
+    } else {
+        u64::from(runtime::get_blocktime()) + fee_expiry
+    };
+    let mut approvals: Vec<Approval> = get_key(&approvals_key(recipient));
+    let count = approvals.len();
+    approvals.retain(|(source, label, _)| *source != entry.0 || *label != entry.1);
+    if approvals.len() != count {
+        set_key(&approvals_key(recipient), approvals);
+    }
+    pending.push((entry, depositor, (fee, expires_at)));
+    set_pending(recipient, pending);
+}
+
+/// Stores the entries pending for `recipient`, dropping the approvals of the entries no longer pending
+/// and the members of its bundles that are no longer pending as deposited by the owner of the bundle.
+/// A bundle left without members is removed.
+fn set_pending(recipient: &Key, pending: Vec<Pending>) {
+    let mut approvals: Vec<Approval> = get_key(&approvals_key(recipient));
+    let count = approvals.len();
+    approvals.retain(|(source, label, _)| {
+        pending
+            .iter()
+            .any(|((pending_source, pending_label, _), _, _)| {
+                pending_source == source && pending_label == label
+            })
+    });
+    if approvals.len() != count {
+        set_key(&approvals_key(recipient), approvals);
+    }
+    let mut bundles: Vec<(String, AccountHash)> = get_key(&bundles_key(recipient));
+    let count = bundles.len();
+    bundles.retain(|(bundle, owner)| {
+        let mut members: Vec<(Option<ContractPackageHash>, String)> =
+            get_key(&bundle_key(recipient, bundle));
+        let count = members.len();
+        members.retain(|(source, label)| {
+            pending
+                .iter()
+                .any(|((pending_source, pending_label, _), depositor, _)| {
+                    pending_source == source && pending_label == label && depositor == owner
+                })
+        });
+        if members.is_empty() {
+            runtime::remove_key(&bundle_key(recipient, bundle));
+            return false;
+        }
+        if members.len() != count {
+            set_key(&bundle_key(recipient, bundle), members);
+        }
+        true
+    });
+    if bundles.len() != count {
+        set_key(&bundles_key(recipient), bundles);
+    }
+    set_key(&pending_key(recipient), pending);
+}
+
+/// Adds the entry `(source, label)`, just deposited by the caller, to `bundle` of `recipient`.
+/// A bundle belongs to the depositor that created it, adding to the bundle of another depositor
+/// reverts with `122`.
+fn add_to_bundle(recipient: &Key, bundle: &str, member: (Option<ContractPackageHash>, String)) {
+    let depositor = runtime::get_caller();
+    let mut bundles: Vec<(String, AccountHash)> = get_key(&bundles_key(recipient));
+    match bundles.iter().find(|(name, _)| name == bundle) {
+        Some((_, owner)) if *owner != depositor => revert(ApiError::User(122)),
+        Some(_) => {}
+        None => {
+            bundles.push((bundle.to_string(), depositor));
+            set_key(&bundles_key(recipient), bundles);
+        }
+    }
+    let mut members: Vec<(Option<ContractPackageHash>, String)> =
+        get_key(&bundle_key(recipient, bundle));
+    if !members.contains(&member) {
+        members.push(member);
+        set_key(&bundle_key(recipient, bundle), members);
+    }
+}
+
+/// Reverts with `error` if one more entry on top of `used` goes over the quota stored under `quota`.
+/// A quota of `0` means there is no limit.
+fn ensure_within_quota(used: u32, quota: &str, error: ApiError) {
+    let quota: u32 = get_key(quota);
+    if quota != 0 && used >= quota {
+        revert(error);
+    }
+}
+
+/// Moves the deposit fee, if one is set, from the callers `purse` into the fee purse.
+/// Reverts with `115` if a fee is due and no `purse` was passed.
+fn collect_fee() -> U512 {
+    let fee: U512 = get_key("fee");
+    if !fee.is_zero() {
+        let purse: URef =
+            get_optional_named_arg(args::PURSE).unwrap_or_revert_with(ApiError::User(115));
+        system::transfer_from_purse_to_purse(purse, fee_purse(), fee, None).unwrap_or_revert();
+    }
+    fee
+}
+
+/// The purse holding the deposit fees.
+fn fee_purse() -> URef {
+    runtime::get_key("fee_purse")
+        .and_then(Key::into_uref)
+        .unwrap_or_revert()
+}
+
+/// Whether the fee paid for `pending` can no longer be refunded.
+fn is_expired((_, _, (_, expires_at)): &Pending) -> bool {
+    *expires_at != 0 && u64::from(runtime::get_blocktime()) >= *expires_at
+}
+
+/// Takes a removed entry off the pending entry counters and pays out its fee,
+/// back to the depositor or, once expired, to the owner.
+fn settle(pending: &Pending) {
+    let (_, depositor, (fee, _)) = pending;
+    release_pending(depositor);
+    if fee.is_zero() {
+        return;
+    }
+    let payee = if is_expired(pending) {
+        get_optional_key::<AccountHash>("owner").unwrap_or_revert()
+    } else {
+        *depositor
+    };
+    system::transfer_from_purse_to_account(fee_purse(), payee, *fee, None).unwrap_or_revert();
+}
+
+/// The entries pending for `recipient`. The URef the first release stored for an account,
+/// under the account hash, is moved over on first access as an `admin` entry without a source,
+/// deposited by the account itself.
+fn pending_entries(recipient: &Key) -> Vec<Pending> {
+    let mut pending: Vec<Pending> = get_key(&pending_key(recipient));
+    let account = match recipient {
+        Key::Account(account) => account,
+        _ => return pending,
+    };
+    if let Some(uref) = get_optional_key::<URef>(&account.to_string()) {
+        runtime::remove_key(&account.to_string());
+        pending.push((
+            (None, DEFAULT_LABEL.to_string(), uref),
+            *account,
+            (U512::zero(), 0),
+        ));
+        set_key(&pending_key(recipient), pending.clone());
+        set_key(
+            &pending_count_key(account),
+            get_key::<u32>(&pending_count_key(account)) + 1,
+        );
+        set_key("total_pending", get_key::<u32>("total_pending") + 1);
+    }
+    pending
+}
+
+/// Takes an entry deposited by `depositor` off the pending entry counters.
+fn release_pending(depositor: &AccountHash) {
+    let depositor_pending: u32 = get_key(&pending_count_key(depositor));
+    set_key(
+        &pending_count_key(depositor),
+        depositor_pending.saturating_sub(1),
+    );
+    set_key(
+        "total_pending",
+        get_key::<u32>("total_pending").saturating_sub(1),
+    );
+}
+
+/// Reverts with `105` unless `approver` is listed in `approvers`, or an `approver_package` was set
+/// and the caller passed a `uref` it confirms. The package is asked the same way `ensure_from_source`
+/// asks source packages, a `uref` outside the groups guarding its `confirm_deposit` fails the call.
+fn ensure_approver(approver: &AccountHash) {
+    if get_key::<Vec<AccountHash>>("approvers").contains(approver) {
+        return;
+    }
+    let uref: Option<URef> = get_optional_named_arg(args::UREF);
+    match (
+        get_key::<Option<ContractPackageHash>>("approver_package"),
+        uref,
+    ) {
+        (Some(approver_package), Some(uref)) => {
+            let _: () = runtime::call_versioned_contract(
+                approver_package,
+                None,
+                entry_points::CONFIRM_DEPOSIT,
+                runtime_args! {args::UREF => uref},
+            );
+        }
+        _ => revert(ApiError::User(105)),
+    }
+}
+
+/// Whether an entry pending for `recipient` gathered the approvals required by the release policy.
+fn released(recipient: &Key) -> impl Fn(&Entry) -> bool {
+    let threshold = get_key::<u8>("approval_threshold") as usize;
+    let approvals: Vec<Approval> = get_key(&approvals_key(recipient));
+    move |(source, label, _): &Entry| {
+        threshold == 0
+            || approvals
+                .iter()
+                .any(|(approved_source, approved_label, approvers)| {
+                    approved_source == source
+                        && approved_label == label
+                        && approvers.len() >= threshold
+                })
+    }
+}
+
+/// Moves the entries pending for `recipient` that match `filter` to its claimed entries,
+/// settling their fees, and returns them.
+fn claim_matching<F: Fn(&Entry) -> bool>(recipient: &Key, filter: F) -> Vec<Entry> {
+    let (claimed, pending): (Vec<Pending>, Vec<Pending>) = pending_entries(recipient)
+        .into_iter()
+        .partition(|(entry, _, _)| filter(entry));
+    set_pending(recipient, pending);
+    let claimed: Vec<Entry> = claimed
+        .into_iter()
+        .map(|pending| {
+            settle(&pending);
+            pending.0
+        })
+        .collect();
+    let mut claimed_before: Vec<Entry> = get_key(&claimed_key(recipient));
+    claimed_before.extend(claimed.iter().cloned());
+    set_key(&claimed_key(recipient), claimed_before);
+    claimed
+}
+
+/// Every entry stored for `account`, pending or already claimed.
+fn held_entries(account: &AccountHash) -> Vec<Entry> {
+    let recipient = Key::Account(*account);
+    let mut entries: Vec<Entry> = pending_entries(&recipient)
+        .into_iter()
+        .map(|(entry, _, _)| entry)
+        .collect();
+    entries.extend(get_key::<Vec<Entry>>(&claimed_key(&recipient)));
+    entries
+}
+
+/// Removes the entry `(source, label)` delegated to `account` and has the source package take back
+/// the URef it issued for it, then does the same for every account `account` delegated it to.
+/// The record goes first, so the recursion ends even if the records were to form a cycle.
+fn remove_delegation(account: &AccountHash, source: ContractPackageHash, label: &str) {
+    let mut delegations: Vec<Delegation> = get_key(&delegations_key(account));
+    let index = match delegations
+        .iter()
+        .position(|(held_source, held_label, _)| *held_source == source && held_label == label)
+    {
+        Some(index) => index,
+        None => return,
+    };
+    let (_, _, (delegator, _, uref)) = delegations.remove(index);
+    set_key(&delegations_key(account), delegations);
+
+    let delegates: Vec<AccountHash> = get_key(&delegates_key(account));
+    for delegate in delegates.iter().filter(|delegate| {
+        find_delegation(delegate, source, label).map(|(_, _, (delegated_by, _, _))| delegated_by)
+            == Some(*account)
+    }) {
+        remove_delegation(delegate, source, label);
+    }
+    prune_delegates(&delegator);
+
+    let recipient = Key::Account(*account);
+    let (removed, pending): (Vec<Pending>, Vec<Pending>) = pending_entries(&recipient)
+        .into_iter()
+        .partition(|((_, _, held), _, _)| held.addr() == uref.addr());
+    removed.iter().for_each(settle);
+    set_pending(&recipient, pending);
+    let mut claimed: Vec<Entry> = get_key(&claimed_key(&recipient));
+    claimed.retain(|(_, _, held)| held.addr() != uref.addr());
+    set_key(&claimed_key(&recipient), claimed);
+
+    let _: () = runtime::call_versioned_contract(
+        source,
+        None,
+        entry_points::REVOKE_DELEGATE_UREF,
+        runtime_args! {args::UREF => uref},
+    );
+}
+
+/// Drops the accounts `delegator` no longer has any delegation in place for from its delegates.
+fn prune_delegates(delegator: &AccountHash) {
+    let mut delegates: Vec<AccountHash> = get_key(&delegates_key(delegator));
+    delegates.retain(|delegate| {
+        get_key::<Vec<Delegation>>(&delegations_key(delegate))
+            .iter()
+            .any(|(_, _, (delegated_by, _, _))| delegated_by == delegator)
+    });
+    set_key(&delegates_key(delegator), delegates);
+}
+
+/// The delegation of the entry `(source, label)` to `account`, if there is one.
+fn find_delegation(
+    account: &AccountHash,
+    source: ContractPackageHash,
+    label: &str,
+) -> Option<Delegation> {
+    get_key::<Vec<Delegation>>(&delegations_key(account))
+        .into_iter()
+        .find(|(held_source, held_label, _)| *held_source == source && held_label == label)
+}
+
+/// The accounts the entry `(source, label)` was delegated to `account` through, its delegator first.
+/// `delegate` keeps the chains free of cycles, the walk stops at a repeated account all the same.
+fn delegation_ancestors(
+    account: &AccountHash,
+    source: ContractPackageHash,
+    label: &str,
+) -> Vec<AccountHash> {
+    let mut ancestors = Vec::new();
+    let mut current = *account;
+    while let Some((_, _, (delegator, _, _))) = find_delegation(&current, source, label) {
+        if ancestors.contains(&delegator) {
+            break;
+        }
+        ancestors.push(delegator);
+        current = delegator;
+    }
+    ancestors
+}
+
+/// Number of delegation hops between `account` and a direct deposit of the entry `(source, label)`.
+fn delegation_depth(account: &AccountHash, source: ContractPackageHash, label: &str) -> u8 {
+    find_delegation(account, source, label)
+        .map(|(_, _, (_, depth, _))| depth)
+        .unwrap_or_default()
+}
+
+// Per recipient and per account storage. Upgrades carry the named keys of the previous version over,
+// so every name includes the version of the layout it belongs to and a value is never read back as
+// another type. Changing one of the types stored means bumping `LAYOUT` and moving the old values over,
+// the way `pending_entries` does for the single URef the first release stored under the account hash.
+// Recipients are named by their formatted `Key`, `account-hash-…` for accounts and `hash-…` for contracts.
+
+/// Version of the storage layout.
+const LAYOUT: &str = "v2";
+
+fn pending_key(recipient: &Key) -> String {
+    format!("{}-pending-{}", LAYOUT, recipient.to_formatted_string())
+}
+
+fn pending_count_key(depositor: &AccountHash) -> String {
+    format!("{}-pending-count-{}", LAYOUT, depositor)
+}
+
+fn claimed_key(recipient: &Key) -> String {
+    format!("{}-claimed-{}", LAYOUT, recipient.to_formatted_string())
+}
+
+fn bundle_key(recipient: &Key, bundle: &str) -> String {
+    format!(
+        "{}-bundle-{}-{}",
+        LAYOUT,
+        bundle,
+        recipient.to_formatted_string()
+    )
+}
+
+fn bundles_key(recipient: &Key) -> String {
+    format!("{}-bundles-{}", LAYOUT, recipient.to_formatted_string())
+}
+
+fn delegations_key(account: &AccountHash) -> String {
+    format!("{}-delegations-{}", LAYOUT, account)
+}
+
+fn delegates_key(account: &AccountHash) -> String {
+    format!("{}-delegates-{}", LAYOUT, account)
+}
+
+fn approvals_key(recipient: &Key) -> String {
+    format!(
+        "{}-entry-approvals-{}",
+        LAYOUT,
+        recipient.to_formatted_string()
+    )
+}
+
 /// Getter function from context storage.
 /// Returns the previously data previously stored under `name` key,
 /// or returns the default value of the type expected at the end of the call.
 fn get_key<T: FromBytes + CLTyped + Default>(name: &str) -> T {
-    match runtime::get_key(name) {
-        None => Default::default(),
-        Some(value) => {
-            let key = value.try_into().unwrap_or_revert();
-            storage::read(key).unwrap_or_revert().unwrap_or_revert()
-        }
-    }
+    get_optional_key(name).unwrap_or_default()
+}
+
+/// Same as `get_key`, but tells apart a missing key from a stored default value.
+fn get_optional_key<T: FromBytes + CLTyped>(name: &str) -> Option<T> {
+    runtime::get_key(name).map(|value| {
+        let key = value.try_into().unwrap_or_revert();
+        storage::read(key).unwrap_or_revert().unwrap_or_revert()
+    })
 }
 
 /// Creates new storage key `name` and stores `value` to it.
@@ -120,5 +1117,5 @@ fn set_key<T: ToBytes + CLTyped>(name: &str, value: T) {
 
 #[no_mangle]
 fn call() {
-    install_or_upgrade_contract(String::from("uref-share"));
+    install_or_upgrade_contract(String::from(keys::SHARE_CONTRACT));
 }
@@ -0,0 +1,456 @@
+// Offline builder of signed deploys for the URef sharing example.
+// Builds the session arguments with `uref_share_client`, signs the deploy with a PEM secret key
+// and writes it as Casper deploy JSON, ready to be sent with `casper-client send-deploy`.
+//
+//     cargo run --release --bin uref-share-cli -- <COMMAND> [OPTIONS]
+mod recipients;
+
+use std::{collections::BTreeMap, env, fs, process};
+
+use casper_client::{DeployStrParams, PaymentStrParams, SessionStrParams};
+use casper_types::{
+    account::AccountHash, bytesrepr::ToBytes, ContractPackageHash, PublicKey, RuntimeArgs,
+    SecretKey, URef,
+};
+use uref_share_client::{entry_points, locked, locked_with_share, share};
+
+const USAGE: &str = "\
+Usage: uref-share-cli <COMMAND> [OPTIONS]
+
+Commands:
+    install       --wasm PATH --recipients FILE [--share-package HASH]
+                  Installs locked.wasm, depositing an admin URef for each recipient in the
+                  sharing contract, or with --edition integrated locked_with_share.wasm.
+    store-uref    --share-package HASH --uref UREF --recipient RECIPIENT [--label LABEL] [--purse UREF]
+                  Deposits UREF for RECIPIENT in the sharing contract, paying the deposit fee
+                  from the purse, which is needed while the sharing contract charges one.
+    append-urefs  --package HASH --urefs UREF,UREF,... --recipients FILE
+                  Deposits a URef for each recipient in locked_with_share.wasm, the first one
+                  for the first recipient of the list and so on.
+    get-access    --package HASH [--share-package HASH] [--bundle NAME]
+                  Claims the admin URef of the caller from the testing contract at HASH,
+                  failing if it deposits in another sharing contract than --share-package.
+
+Options:
+    --secret-key PATH       PEM secret key the deploy is signed with, required
+    --chain-name NAME       Name of the chain the deploy is for, required
+    --payment-amount MOTES  Payment for the deploy, required
+    --edition EDITION       `standalone` (default) or `integrated`
+    --output PATH           Deploy JSON to write, `deploy.json` by default
+    --force                 Overwrite the output if it exists
+    --ttl DURATION          Time to live of the deploy, `30min` by default
+    --gas-price PRICE       Gas price of the deploy, `1` by default
+    --timestamp TIME        RFC3339 timestamp of the deploy, now by default
+
+Package hashes are hex encoded, optionally prefixed with `hash-` or `contract-package-wasm`.
+Recipients are hex encoded public keys or formatted account hashes. Recipient lists are CSV
+files with the recipient in the first column, or JSON arrays of strings for `.json` files.";
+
+const DEFAULT_OUTPUT: &str = "deploy.json";
+const DEFAULT_TTL: &str = "30min";
+const DEFAULT_GAS_PRICE: &str = "1";
+
+/// Which testing contract the deploy targets.
+#[derive(Clone, Copy)]
+enum Edition {
+    /// `locked.wasm`, storing its URefs in `uref-share.wasm`.
+    Standalone,
+    /// `locked_with_share.wasm`, storing its URefs itself.
+    Integrated,
+}
+
+/// Code the deploy runs.
+enum Session {
+    /// An installer wasm file.
+    Wasm(String),
+    /// An entry point of the latest version of a contract package, by its hex encoded hash.
+    Package {
+        hash: String,
+        entry_point: &'static str,
+    },
+}
+
+struct Options {
+    values: BTreeMap<String, String>,
+    force: bool,
+}
+
+impl Options {
+    fn required(&self, name: &str) -> Result<&str, String> {
+        self.optional(name)
+            .ok_or_else(|| format!("{} is required", name))
+    }
+
+    fn optional(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(String::as_str)
+    }
+}
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let command = args.next().unwrap_or_default();
+    if command.is_empty() || command == "--help" {
+        println!("{}", USAGE);
+        return;
+    }
+    if let Err(error) = parse_options(args).and_then(|options| run(&command, &options)) {
+        eprintln!("error: {}\n\n{}", error, USAGE);
+        process::exit(1);
+    }
+}
+
+fn run(command: &str, options: &Options) -> Result<(), String> {
+    let (session, runtime_args) = match command {
+        "install" => install(options)?,
+        "store-uref" => store_uref(options)?,
+        "append-urefs" => append_urefs(options)?,
+        "get-access" => get_access(options)?,
+        _ => return Err(format!("unknown command {}", command)),
+    };
+    write_deploy(options, &session, &runtime_args)
+}
+
+fn install(options: &Options) -> Result<(Session, RuntimeArgs), String> {
+    let wasm = options.required("--wasm")?.to_string();
+    let (users, user_hashes) =
+        recipients::split(recipients::read(options.required("--recipients")?)?);
+    let runtime_args = match edition(options)? {
+        Edition::Standalone => {
+            let (_, share_hash) = package_hash(options.required("--share-package")?)?;
+            locked::InstallArgs::new(users, share_hash)
+                .user_hashes(user_hashes)
+                .build()
+        }
+        Edition::Integrated => {
+            if !user_hashes.is_empty() {
+                return Err("the integrated edition only deposits for public keys".to_string());
+            }
+            locked_with_share::InstallArgs::new(users).build()
+        }
+    };
+    Ok((Session::Wasm(wasm), runtime_args))
+}
+
+fn store_uref(options: &Options) -> Result<(Session, RuntimeArgs), String> {
+    let (hash, _) = package_hash(options.required("--share-package")?)?;
+    let recipient = recipients::parse(options.required("--recipient")?)?;
+    let mut store_uref = share::StoreUref::new(recipient, uref(options.required("--uref")?)?);
+    if let Some(label) = options.optional("--label") {
+        store_uref = store_uref.label(label);
+    }
+    if let Some(purse) = options.optional("--purse") {
+        store_uref = store_uref.purse(uref(purse)?);
+    }
+    let session = Session::Package {
+        hash,
+        entry_point: entry_points::STORE_UREF,
+    };
+    Ok((session, store_uref.build()))
+}
+
+fn append_urefs(options: &Options) -> Result<(Session, RuntimeArgs), String> {
+    let (hash, _) = package_hash(options.required("--package")?)?;
+    let urefs = options
+        .required("--urefs")?
+        .split(',')
+        .map(|value| uref(value.trim()))
+        .collect::<Result<Vec<URef>, String>>()?;
+    let recipients = recipients::read(options.required("--recipients")?)?;
+    if urefs.len() != recipients.len() {
+        return Err(format!(
+            "{} URefs for {} recipients, pass one for each",
+            urefs.len(),
+            recipients.len()
+        ));
+    }
+    let session = Session::Package {
+        hash,
+        entry_point: entry_points::APPEND_UREFS,
+    };
+    Ok((
+        session,
+        locked_with_share::append_urefs(urefs, recipients::as_keys(&recipients)),
+    ))
+}
+
+fn get_access(options: &Options) -> Result<(Session, RuntimeArgs), String> {
+    let (hash, package) = package_hash(options.required("--package")?)?;
+    let runtime_args = match edition(options)? {
+        Edition::Standalone => {
+            let share_contract = match options.optional("--share-package") {
+                Some(share_package) => Some(package_hash(share_package)?.1),
+                None => None,
+            };
+            locked::get_access(package, share_contract, options.optional("--bundle"))
+        }
+        Edition::Integrated => locked_with_share::get_access(package),
+    };
+    let session = Session::Package {
+        hash,
+        entry_point: entry_points::GET_ACCESS,
+    };
+    Ok((session, runtime_args))
+}
+
+/// Signs the deploy and writes it to the output. The session arguments are handed to the
+/// client `ToBytes` encoded, as they hold lists and keys its simple arguments can't express.
+fn write_deploy(
+    options: &Options,
+    session: &Session,
+    runtime_args: &RuntimeArgs,
+) -> Result<(), String> {
+    let secret_key_path = options.required("--secret-key")?;
+    let secret_key = SecretKey::from_file(secret_key_path)
+        .map_err(|error| format!("can't read the secret key {}: {}", secret_key_path, error))?;
+    let chain_name = options.required("--chain-name")?;
+    let payment_amount = options.required("--payment-amount")?;
+    let output = options.optional("--output").unwrap_or(DEFAULT_OUTPUT);
+
+    let args_path = env::temp_dir().join(format!("uref-share-cli-{}.args", process::id()));
+    let bytes = runtime_args
+        .to_bytes()
+        .map_err(|error| format!("can't serialize the session arguments: {}", error))?;
+    fs::write(&args_path, bytes)
+        .map_err(|error| format!("can't write {}: {}", args_path.display(), error))?;
+    let args_file = args_path.to_string_lossy().into_owned();
+
+    let deploy_params = DeployStrParams {
+        secret_key: secret_key_path,
+        timestamp: options.optional("--timestamp").unwrap_or(""),
+        ttl: options.optional("--ttl").unwrap_or(DEFAULT_TTL),
+        gas_price: options.optional("--gas-price").unwrap_or(DEFAULT_GAS_PRICE),
+        dependencies: Vec::new(),
+        chain_name,
+    };
+    let session_params = match session {
+        Session::Wasm(path) => SessionStrParams::with_path(path, Vec::new(), &args_file),
+        Session::Package { hash, entry_point } => {
+            SessionStrParams::with_package_hash(hash, "", entry_point, Vec::new(), &args_file)
+        }
+    };
+    let payment_params = PaymentStrParams::with_amount(payment_amount);
+    let written = casper_client::make_deploy(
+        output,
+        deploy_params,
+        session_params,
+        payment_params,
+        options.force,
+    );
+    let _ = fs::remove_file(&args_path);
+    written.map_err(|error| format!("can't write the deploy to {}: {}", output, error))?;
+
+    let signer = AccountHash::from(&PublicKey::from(&secret_key));
+    println!(
+        "wrote {}, signed by {}",
+        output,
+        signer.to_formatted_string()
+    );
+    Ok(())
+}
+
+fn edition(options: &Options) -> Result<Edition, String> {
+    match options.optional("--edition").unwrap_or("standalone") {
+        "standalone" => Ok(Edition::Standalone),
+        "integrated" => Ok(Edition::Integrated),
+        edition => Err(format!("unknown edition {}", edition)),
+    }
+}
+
+/// Parses a package hash, returning it hex encoded as the client expects it, and decoded.
+fn package_hash(value: &str) -> Result<(String, ContractPackageHash), String> {
+    let hex = value
+        .trim_start_matches("hash-")
+        .trim_start_matches("contract-package-wasm");
+    let package_hash =
+        ContractPackageHash::from_formatted_str(&format!("contract-package-wasm{}", hex))
+            .map_err(|_| format!("{} is not a contract package hash", value))?;
+    Ok((hex.to_string(), package_hash))
+}
+
+fn uref(value: &str) -> Result<URef, String> {
+    URef::from_formatted_str(value).map_err(|_| format!("{} is not a formatted URef", value))
+}
+
+fn parse_options(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        values: BTreeMap::new(),
+        force: false,
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--force" => options.force = true,
+            "--wasm" | "--recipients" | "--recipient" | "--share-package" | "--package"
+            | "--uref" | "--urefs" | "--purse" | "--label" | "--bundle" | "--secret-key"
+            | "--chain-name" | "--payment-amount" | "--edition" | "--output" | "--ttl"
+            | "--gas-price" | "--timestamp" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| format!("{} needs a value", arg))?;
+                options.values.insert(arg, value);
+            }
+            _ => return Err(format!("unknown argument {}", arg)),
+        }
+    }
+    Ok(options)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use casper_types::{AccessRights, CLValue, Key};
+    use uref_share_client::{args, Recipient, Recipients};
+
+    use super::*;
+
+    fn options(values: &[(&str, &str)]) -> Options {
+        Options {
+            values: values
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            force: true,
+        }
+    }
+
+    /// Path of `name` in the temp directory, apart from the files of other test runs.
+    fn temp_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("uref-share-cli-test-{}-{}", process::id(), name))
+    }
+
+    fn formatted_uref(seed: u8) -> (URef, String) {
+        let uref = URef::new([seed; 32], AccessRights::READ_ADD_WRITE);
+        (uref, uref.to_formatted_string())
+    }
+
+    #[test]
+    fn package_hash_accepts_every_prefix() {
+        let hex = "2a".repeat(32);
+        for value in [
+            hex.clone(),
+            format!("hash-{}", hex),
+            format!("contract-package-wasm{}", hex),
+        ]
+        .iter()
+        {
+            let (encoded, package) = package_hash(value).unwrap();
+            assert_eq!(encoded, hex);
+            assert_eq!(package, ContractPackageHash::new([0x2a; 32]));
+        }
+        assert!(package_hash("hash-2a").is_err());
+        assert!(package_hash(&format!("uref-{}", hex)).is_err());
+    }
+
+    #[test]
+    fn store_uref_pays_from_the_purse() {
+        let account = AccountHash::new([1; 32]);
+        let (uref, formatted) = formatted_uref(2);
+        let (purse, formatted_purse) = formatted_uref(3);
+        let share_package = "2a".repeat(32);
+        let recipient = account.to_formatted_string();
+        let mut values = vec![
+            ("--share-package", share_package.as_str()),
+            ("--recipient", recipient.as_str()),
+            ("--uref", formatted.as_str()),
+        ];
+
+        let (_, runtime_args) = store_uref(&options(&values)).unwrap();
+        assert_eq!(runtime_args.get(args::PURSE), None);
+
+        values.push(("--purse", formatted_purse.as_str()));
+        let (_, runtime_args) = store_uref(&options(&values)).unwrap();
+        assert_eq!(
+            runtime_args,
+            share::StoreUref::new(Recipient::AccountHash(account), uref)
+                .purse(purse)
+                .build()
+        );
+    }
+
+    #[test]
+    fn append_urefs_passes_a_uref_for_each_recipient() {
+        let accounts = [AccountHash::new([1; 32]), AccountHash::new([2; 32])];
+        let list = temp_path("append-recipients.csv");
+        fs::write(
+            &list,
+            format!(
+                "recipient\n{}\n{}\n",
+                accounts[0].to_formatted_string(),
+                accounts[1].to_formatted_string()
+            ),
+        )
+        .unwrap();
+        let (first, first_formatted) = formatted_uref(3);
+        let (second, second_formatted) = formatted_uref(4);
+        let package = "2a".repeat(32);
+        let urefs = format!("{}, {}", first_formatted, second_formatted);
+        let list_path = list.to_string_lossy().into_owned();
+        let mut values = vec![
+            ("--package", package.as_str()),
+            ("--recipients", list_path.as_str()),
+            ("--urefs", urefs.as_str()),
+        ];
+
+        let (_, runtime_args) = append_urefs(&options(&values)).unwrap();
+        assert_eq!(
+            runtime_args,
+            locked_with_share::append_urefs(
+                vec![first, second],
+                Recipients::Keys(accounts.iter().copied().map(Key::Account).collect()),
+            )
+        );
+
+        values[2] = ("--urefs", first_formatted.as_str());
+        assert!(append_urefs(&options(&values)).is_err());
+        fs::remove_file(&list).unwrap();
+    }
+
+    #[test]
+    fn writes_a_signed_deploy() {
+        let secret_key_path = temp_path("secret_key.pem");
+        SecretKey::ed25519_from_bytes([7; 32])
+            .unwrap()
+            .to_file(&secret_key_path)
+            .unwrap();
+        let output = temp_path("deploy.json");
+        let secret_key = secret_key_path.to_string_lossy().into_owned();
+        let output_path = output.to_string_lossy().into_owned();
+        let options = options(&[
+            ("--secret-key", secret_key.as_str()),
+            ("--chain-name", "casper-test"),
+            ("--payment-amount", "100000000000"),
+            ("--output", output_path.as_str()),
+        ]);
+        let session = Session::Package {
+            hash: "2a".repeat(32),
+            entry_point: entry_points::STORE_UREF,
+        };
+        let runtime_args = share::StoreUref::new(
+            Recipient::AccountHash(AccountHash::new([1; 32])),
+            formatted_uref(2).0,
+        )
+        .build();
+
+        write_deploy(&options, &session, &runtime_args).unwrap();
+        let deploy: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&output).unwrap()).unwrap();
+        assert_eq!(deploy["header"]["chain_name"], "casper-test");
+        assert_eq!(deploy["approvals"].as_array().unwrap().len(), 1);
+        let call = &deploy["session"]["StoredVersionedContractByHash"];
+        assert_eq!(call["entry_point"], entry_points::STORE_UREF);
+        let passed: Vec<&str> = call["args"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|arg| arg[0].as_str().unwrap())
+            .collect();
+        assert_eq!(passed, vec![args::ACCOUNT_HASH, args::UREF]);
+        assert_eq!(
+            call["args"][1][1]["bytes"],
+            serde_json::to_value(CLValue::from_t(formatted_uref(2).0).unwrap()).unwrap()["bytes"]
+        );
+
+        fs::remove_file(&secret_key_path).unwrap();
+        fs::remove_file(&output).unwrap();
+    }
+}
@@ -0,0 +1,171 @@
+//! Reading the accounts to deposit for from a recipient list.
+//!
+//! A list is either a JSON array of strings, for files ending in `.json`, or a CSV file with
+//! the recipient in the first column. Empty lines, lines starting with `#` and a `recipient`
+//! header are skipped. Each recipient is a hex encoded public key or a formatted account hash.
+use std::{fs, path::Path};
+
+use casper_types::{account::AccountHash, Key, PublicKey};
+use uref_share_client::{Recipient, Recipients};
+
+/// Header line a CSV recipient list may start with.
+const CSV_HEADER: &str = "recipient";
+
+/// Reads the recipient list at `path`.
+pub fn read(path: &str) -> Result<Vec<Recipient>, String> {
+    let contents =
+        fs::read_to_string(path).map_err(|error| format!("can't read {}: {}", path, error))?;
+    let values = if Path::new(path)
+        .extension()
+        .map_or(false, |ext| ext == "json")
+    {
+        serde_json::from_str::<Vec<String>>(&contents)
+            .map_err(|error| format!("{} is not a JSON array of strings: {}", path, error))?
+    } else {
+        read_csv(&contents)
+    };
+    if values.is_empty() {
+        return Err(format!("{} lists no recipients", path));
+    }
+    values.iter().map(|value| parse(value)).collect()
+}
+
+/// Parses a single recipient, a hex encoded public key or a formatted account hash.
+pub fn parse(value: &str) -> Result<Recipient, String> {
+    if let Ok(account_hash) = AccountHash::from_formatted_str(value) {
+        return Ok(Recipient::AccountHash(account_hash));
+    }
+    PublicKey::from_hex(value)
+        .map(Recipient::PublicKey)
+        .map_err(|_| {
+            format!(
+                "{} is neither a hex encoded public key nor an account hash",
+                value
+            )
+        })
+}
+
+/// All recipients as account keys, for `append_urefs` taking a mix of public keys and hashes.
+pub fn as_keys(recipients: &[Recipient]) -> Recipients {
+    Recipients::Keys(
+        recipients
+            .iter()
+            .map(|recipient| Key::Account(account_hash(recipient)))
+            .collect(),
+    )
+}
+
+/// Splits the recipients into the ones known by their public key and the ones only known
+/// by their account hash, the `users` and `user_hashes` install arguments.
+pub fn split(recipients: Vec<Recipient>) -> (Vec<PublicKey>, Vec<AccountHash>) {
+    let mut public_keys = Vec::new();
+    let mut account_hashes = Vec::new();
+    for recipient in recipients {
+        match recipient {
+            Recipient::PublicKey(public_key) => public_keys.push(public_key),
+            recipient => account_hashes.push(account_hash(&recipient)),
+        }
+    }
+    (public_keys, account_hashes)
+}
+
+fn account_hash(recipient: &Recipient) -> AccountHash {
+    match recipient {
+        Recipient::PublicKey(public_key) => AccountHash::from(public_key),
+        Recipient::AccountHash(account_hash) => *account_hash,
+        Recipient::Key(key) => key
+            .into_account()
+            .expect("recipient lists only hold accounts"),
+    }
+}
+
+fn read_csv(contents: &str) -> Vec<String> {
+    contents
+        .lines()
+        .map(|line| line.split(',').next().unwrap_or_default().trim())
+        .filter(|value| !value.is_empty() && !value.starts_with('#') && *value != CSV_HEADER)
+        .map(|value| value.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use casper_types::SecretKey;
+
+    use super::*;
+
+    fn public_key(seed: u8) -> PublicKey {
+        PublicKey::from(&SecretKey::ed25519_from_bytes([seed; 32]).unwrap())
+    }
+
+    #[test]
+    fn csv_lists_skip_headers_comments_and_blank_lines() {
+        let contents = "recipient,note\n# team a\n\n  first , lead\nsecond\n";
+        assert_eq!(read_csv(contents), vec!["first", "second"]);
+    }
+
+    #[test]
+    fn parses_public_keys_and_account_hashes() {
+        let public_key = public_key(1);
+        assert_eq!(
+            parse(&public_key.to_hex()),
+            Ok(Recipient::PublicKey(public_key))
+        );
+        let account_hash = AccountHash::new([2; 32]);
+        assert_eq!(
+            parse(&account_hash.to_formatted_string()),
+            Ok(Recipient::AccountHash(account_hash))
+        );
+        assert!(parse("alice").is_err());
+    }
+
+    #[test]
+    fn reads_json_lists() {
+        let path =
+            env::temp_dir().join(format!("uref-share-cli-{}-recipients.json", process::id()));
+        let account_hash = AccountHash::new([2; 32]);
+        fs::write(
+            &path,
+            format!(
+                "[\"{}\", \"{}\"]",
+                public_key(1).to_hex(),
+                account_hash.to_formatted_string()
+            ),
+        )
+        .unwrap();
+        let path_str = path.to_string_lossy().into_owned();
+        assert_eq!(
+            read(&path_str),
+            Ok(vec![
+                Recipient::PublicKey(public_key(1)),
+                Recipient::AccountHash(account_hash)
+            ])
+        );
+
+        fs::write(&path, "[]").unwrap();
+        assert_eq!(
+            read(&path_str),
+            Err(format!("{} lists no recipients", path_str))
+        );
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn splits_public_keys_from_account_hashes() {
+        let account_hash = AccountHash::new([2; 32]);
+        let recipients = vec![
+            Recipient::AccountHash(account_hash),
+            Recipient::PublicKey(public_key(1)),
+            Recipient::Key(Key::Account(AccountHash::new([3; 32]))),
+        ];
+        assert_eq!(
+            split(recipients),
+            (
+                vec![public_key(1)],
+                vec![account_hash, AccountHash::new([3; 32])]
+            )
+        );
+    }
+}
@@ -0,0 +1,51 @@
+// Exports the entry points every contract installs as JSON, keyed by the contract name.
+// Each entry point lists its arguments with their `CLType`, its return type, access and type,
+// in the form the node reports them in.
+//
+//     cargo run --release --bin uref-share-schema -- [--output PATH]
+use std::{env, fs, process};
+
+use casper_types::EntryPoints;
+use serde_json::{Map, Value};
+use uref_share_client::{keys, schema};
+
+const CONTRACTS: [(&str, fn() -> EntryPoints); 4] = [
+    (keys::SHARE_CONTRACT, schema::share),
+    (keys::LOCKED_CONTRACT, schema::locked),
+    (keys::LOCKED_WITH_SHARE_CONTRACT, schema::locked_with_share),
+    (keys::RECEIVER_CONTRACT, schema::receiver),
+];
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let output = match (args.next().as_deref(), args.next()) {
+        (None, _) => None,
+        (Some("--output"), Some(path)) => Some(path),
+        _ => {
+            eprintln!("Usage: uref-share-schema [--output PATH]");
+            process::exit(1);
+        }
+    };
+
+    let mut contracts = Map::new();
+    for (name, entry_points) in CONTRACTS.iter() {
+        let entry_points = entry_points()
+            .take_entry_points()
+            .into_iter()
+            .map(|entry_point| {
+                serde_json::to_value(entry_point).expect("entry points should serialize")
+            })
+            .collect();
+        contracts.insert(name.to_string(), Value::Array(entry_points));
+    }
+    let json = serde_json::to_string_pretty(&Value::Object(contracts))
+        .expect("entry points should serialize");
+
+    match output {
+        Some(path) => fs::write(&path, json).unwrap_or_else(|error| {
+            eprintln!("can't write {}: {}", path, error);
+            process::exit(1);
+        }),
+        None => println!("{}", json),
+    }
+}
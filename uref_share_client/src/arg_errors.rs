@@ -0,0 +1,102 @@
+//! User errors the contracts revert with when they can't read an argument.
+//!
+//! Every argument has two codes of its own: `missing` when a required argument was not passed,
+//! and the one after it, `invalid`, when the value does not deserialize as the declared type.
+//! Only the serialized value reaches the contract, so a value of another type serializing the same,
+//! like an `AccountHash` passed as a `ContractPackageHash`, can't be told apart.
+use casper_types::ApiError;
+
+use crate::args;
+
+/// Code of the first argument, above the codes the contracts revert with otherwise.
+pub const FIRST_CODE: u16 = 200;
+
+/// Every argument, its position setting its codes. Only ever append, so codes stay stable.
+pub const ARGUMENTS: [&str; 45] = [
+    args::ACCOUNT_PUBKEY,
+    args::ACCOUNT_HASH,
+    args::RECIPIENT,
+    args::ACCOUNT_PUBKEYS,
+    args::ACCOUNT_HASHES,
+    args::RECIPIENTS,
+    args::UREF,
+    args::UREFS,
+    args::LABEL,
+    args::SOURCE_PACKAGE,
+    args::CONTRACT_PACKAGE,
+    args::BUNDLE,
+    args::PURSE,
+    args::SHARE_HASH,
+    args::SHARE_CONTRACT,
+    args::THIS_CONTRACT,
+    args::LOCKED_PACKAGE,
+    args::MAX_DELEGATION_DEPTH,
+    args::APPROVERS,
+    args::APPROVAL_THRESHOLD,
+    args::MAX_PENDING_PER_DEPOSITOR,
+    args::MAX_ENTRIES_PER_RECIPIENT,
+    args::MAX_TOTAL_ENTRIES,
+    args::FEE,
+    args::FEE_EXPIRY,
+    args::NEW_OWNER,
+    args::PACKAGE_ACCESS,
+    args::USERS,
+    args::USER_HASHES,
+    args::CONTRACTS,
+    args::CONTRACTORS,
+    args::LEASE_DURATION,
+    args::RENEWABLE,
+    args::ALLOWANCES,
+    args::ACCOUNT,
+    args::NEW_ACCOUNT,
+    args::ROLE,
+    args::NOTE,
+    args::CALLS,
+    args::GUARDIANS,
+    args::THRESHOLD,
+    args::SHARE_VERSION,
+    args::FEE_BUDGET,
+    args::APPROVER_PACKAGE,
+    args::ADMIN_APPROVERS,
+];
+
+/// Why an argument could not be read.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ArgError {
+    /// A required argument was not passed.
+    Missing,
+    /// The value does not deserialize as the declared type.
+    Invalid,
+}
+
+/// User error code for `error` reading the argument `name`, `None` for unknown arguments.
+pub fn code(name: &str, error: ArgError) -> Option<u16> {
+    let position = ARGUMENTS.iter().position(|argument| *argument == name)? as u16;
+    let code = FIRST_CODE + 2 * position;
+    Some(match error {
+        ArgError::Missing => code,
+        ArgError::Invalid => code + 1,
+    })
+}
+
+/// The error to revert with for `error` reading the argument `name`. Unknown arguments revert
+/// with the generic `MissingArgument` and `InvalidArgument`.
+pub fn api_error(name: &str, error: ArgError) -> ApiError {
+    match (code(name, error), error) {
+        (Some(code), _) => ApiError::User(code),
+        (None, ArgError::Missing) => ApiError::MissingArgument,
+        (None, ArgError::Invalid) => ApiError::InvalidArgument,
+    }
+}
+
+/// The argument and what went wrong reading it, for a user error `code`.
+pub fn describe(code: u16) -> Option<(&'static str, ArgError)> {
+    let offset = code.checked_sub(FIRST_CODE)?;
+    let argument = ARGUMENTS.get(usize::from(offset / 2))?;
+    let error = if offset % 2 == 0 {
+        ArgError::Missing
+    } else {
+        ArgError::Invalid
+    };
+    Some((argument, error))
+}
@@ -0,0 +1,66 @@
+//! Names of the runtime arguments the contracts read.
+
+// Recipients
+pub const ACCOUNT_PUBKEY: &str = "account_pubkey";
+pub const ACCOUNT_HASH: &str = "account_hash";
+pub const RECIPIENT: &str = "recipient";
+pub const ACCOUNT_PUBKEYS: &str = "account_pubkeys";
+pub const ACCOUNT_HASHES: &str = "account_hashes";
+pub const RECIPIENTS: &str = "recipients";
+
+// Entries
+pub const UREF: &str = "uref";
+pub const UREFS: &str = "urefs";
+pub const LABEL: &str = "label";
+pub const SOURCE_PACKAGE: &str = "source_package";
+pub const CONTRACT_PACKAGE: &str = "contract_package";
+pub const BUNDLE: &str = "bundle";
+pub const PURSE: &str = "purse";
+
+// Contracts to call
+pub const SHARE_HASH: &str = "share_hash";
+pub const SHARE_CONTRACT: &str = "share_contract";
+pub const THIS_CONTRACT: &str = "this_contract";
+pub const LOCKED_PACKAGE: &str = "locked_package";
+pub const SHARE_VERSION: &str = "share_version";
+
+// Sharing contract settings
+pub const MAX_DELEGATION_DEPTH: &str = "max_delegation_depth";
+pub const APPROVERS: &str = "approvers";
+pub const APPROVAL_THRESHOLD: &str = "approval_threshold";
+pub const APPROVER_PACKAGE: &str = "approver_package";
+pub const MAX_PENDING_PER_DEPOSITOR: &str = "max_pending_per_depositor";
+pub const MAX_ENTRIES_PER_RECIPIENT: &str = "max_entries_per_recipient";
+pub const MAX_TOTAL_ENTRIES: &str = "max_total_entries";
+/// The quota settings, both as install arguments and `set_quotas` arguments.
+pub const QUOTAS: [&str; 3] = [
+    MAX_PENDING_PER_DEPOSITOR,
+    MAX_ENTRIES_PER_RECIPIENT,
+    MAX_TOTAL_ENTRIES,
+];
+pub const FEE: &str = "fee";
+pub const FEE_EXPIRY: &str = "fee_expiry";
+
+// Ownership
+pub const NEW_OWNER: &str = "new_owner";
+pub const PACKAGE_ACCESS: &str = "package_access";
+
+// Testing contract settings
+pub const USERS: &str = "users";
+pub const USER_HASHES: &str = "user_hashes";
+pub const CONTRACTS: &str = "contracts";
+pub const CONTRACTORS: &str = "contractors";
+pub const LEASE_DURATION: &str = "lease_duration";
+pub const RENEWABLE: &str = "renewable";
+pub const ALLOWANCES: &str = "allowances";
+pub const FEE_BUDGET: &str = "fee_budget";
+pub const ADMIN_APPROVERS: &str = "admin_approvers";
+
+// Access management of the testing contracts
+pub const ACCOUNT: &str = "account";
+pub const NEW_ACCOUNT: &str = "new_account";
+pub const ROLE: &str = "role";
+pub const NOTE: &str = "note";
+pub const CALLS: &str = "calls";
+pub const GUARDIANS: &str = "guardians";
+pub const THRESHOLD: &str = "threshold";
@@ -0,0 +1,60 @@
+//! Decoders for the values the entry points return.
+use alloc::{string::String, vec::Vec};
+
+use casper_types::{
+    account::AccountHash, contracts::ContractVersion, CLValue, CLValueError, ContractPackageHash,
+    URef,
+};
+
+use crate::Entry;
+
+/// `(pending entries deposited by, pending entries for, all pending entries)`, returned by `get_usage`.
+pub type Usage = (u32, u32, u32);
+/// A request to join a group, as `(account, role, note)`.
+pub type AccessRequest = (AccountHash, String, String);
+/// The sharing contract `locked.wasm` deposits in and its pinned version, `None` for the latest one,
+/// returned by `share_binding`.
+pub type ShareBinding = (ContractPackageHash, Option<ContractVersion>);
+/// The entry the sharing contract is pushing to a contract, as `(recipient package, entry)`,
+/// returned by `pushed_entry`.
+pub type Push = (ContractPackageHash, Entry);
+
+/// `retrieve_uref` of the sharing contract, the default URef if nothing is pending.
+pub fn uref(value: CLValue) -> Result<URef, CLValueError> {
+    value.into_t()
+}
+
+/// `retrieve_urefs` of `locked_with_share.wasm`.
+pub fn urefs(value: CLValue) -> Result<Vec<URef>, CLValueError> {
+    value.into_t()
+}
+
+/// `claim_urefs` and `claim_bundle` of the sharing contract.
+pub fn entries(value: CLValue) -> Result<Vec<Entry>, CLValueError> {
+    value.into_t()
+}
+
+/// `get_usage` of the sharing contract.
+pub fn usage(value: CLValue) -> Result<Usage, CLValueError> {
+    value.into_t()
+}
+
+/// `check_access` and `roles_of` of `locked.wasm`.
+pub fn roles(value: CLValue) -> Result<Vec<String>, CLValueError> {
+    value.into_t()
+}
+
+/// `pushed_entry` of the sharing contract.
+pub fn pushed_entry(value: CLValue) -> Result<Option<Push>, CLValueError> {
+    value.into_t()
+}
+
+/// `share_binding` of `locked.wasm`.
+pub fn share_binding(value: CLValue) -> Result<ShareBinding, CLValueError> {
+    value.into_t()
+}
+
+/// `list_access_requests` of `locked_with_share.wasm`.
+pub fn access_requests(value: CLValue) -> Result<Vec<AccessRequest>, CLValueError> {
+    value.into_t()
+}
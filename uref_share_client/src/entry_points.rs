@@ -0,0 +1,59 @@
+//! Names of the entry points of the contracts.
+
+// Sharing contract, `uref-share.wasm`
+pub const RETRIEVE_UREF: &str = "retrieve_uref";
+pub const CLAIM_UREFS: &str = "claim_urefs";
+pub const CLAIM_BUNDLE: &str = "claim_bundle";
+pub const STORE_UREF: &str = "store_uref";
+pub const STORE_UREF_FOR_CONTRACT: &str = "store_uref_for_contract";
+pub const DELEGATE: &str = "delegate";
+pub const REVOKE_DELEGATION: &str = "revoke_delegation";
+pub const SET_QUOTAS: &str = "set_quotas";
+pub const GET_USAGE: &str = "get_usage";
+pub const SET_FEE: &str = "set_fee";
+pub const RECLAIM_UREF: &str = "reclaim_uref";
+pub const EXPIRE_ENTRIES: &str = "expire_entries";
+pub const VERSION: &str = "version";
+pub const PUSHED_ENTRY: &str = "pushed_entry";
+
+// Shared by the sharing contract and `locked_with_share.wasm`
+pub const APPROVE_RELEASE: &str = "approve_release";
+pub const PAUSE: &str = "pause";
+pub const UNPAUSE: &str = "unpause";
+pub const TRANSFER_OWNERSHIP: &str = "transfer_ownership";
+pub const ACCEPT_OWNERSHIP: &str = "accept_ownership";
+
+// Shared by both testing contracts
+pub const GROUP_ACCESS_ONLY: &str = "group_access_only";
+pub const GET_ACCESS: &str = "get_access";
+
+// Testing contract, `locked.wasm`
+pub const CHECK_ACCESS: &str = "check_access";
+pub const ROLES_OF: &str = "roles_of";
+pub const GET_ROLES: &str = "get_roles";
+pub const METERED_ACCESS: &str = "metered_access";
+pub const SET_ALLOWANCE: &str = "set_allowance";
+pub const TOP_UP: &str = "top_up";
+pub const RESET_USAGE: &str = "reset_usage";
+pub const REGISTER_GUARDIANS: &str = "register_guardians";
+pub const DECLARE_LOST: &str = "declare_lost";
+pub const SWEEP_EXPIRED: &str = "sweep_expired";
+pub const RENEW: &str = "renew";
+pub const SHARE_BINDING: &str = "share_binding";
+pub const CONFIRM_DEPOSIT: &str = "confirm_deposit";
+pub const ISSUE_DELEGATE_UREF: &str = "issue_delegate_uref";
+pub const REVOKE_DELEGATE_UREF: &str = "revoke_delegate_uref";
+
+// Testing contract with the sharing feature integrated, `locked_with_share.wasm`
+pub const RETRIEVE_UREFS: &str = "retrieve_urefs";
+pub const APPEND_UREFS: &str = "append_urefs";
+pub const REQUEST_ACCESS: &str = "request_access";
+pub const LIST_ACCESS_REQUESTS: &str = "list_access_requests";
+pub const GET_ACCESS_REQUESTS: &str = "get_access_requests";
+pub const APPROVE_ACCESS: &str = "approve_access";
+pub const DENY_ACCESS: &str = "deny_access";
+pub const CONFIRM_APPROVER: &str = "confirm_approver";
+
+// Example receiver contract, `receiver.wasm`
+pub const RECEIVE_UREF: &str = "receive_uref";
+pub const CALL_LOCKED: &str = "call_locked";
@@ -0,0 +1,68 @@
+//! Named keys the contracts put into the account installing them, and the ones URefs are claimed under.
+use alloc::{format, string::String};
+
+use casper_types::ContractPackageHash;
+
+/// Name each contract installs under, the prefix of the named keys below.
+pub const SHARE_CONTRACT: &str = "uref-share";
+pub const LOCKED_CONTRACT: &str = "locked";
+pub const LOCKED_WITH_SHARE_CONTRACT: &str = "locked-with-share";
+pub const RECEIVER_CONTRACT: &str = "uref-receiver";
+
+/// The `admin` URef the testing contracts keep for their installer.
+pub const DEPLOYER_ADMIN_ACCESS: &str = "locked_deployer_admin_access";
+/// The pending access requests `get_access_requests` of `locked-with-share` copies into the callers account.
+pub const ACCESS_REQUESTS: &str = "access-requests";
+/// The roles `get_roles` of `locked` copies into the callers account.
+pub const ROLES: &str = "roles";
+/// Label of the package access URef handed over through the sharing contract on an ownership transfer.
+pub const PACKAGE_ACCESS: &str = "package-access";
+
+/// The contract package hash.
+pub fn package_hash(name: &str) -> String {
+    format!("{}-package-hash", name)
+}
+
+/// The contract package hash, wrapped in a URef so the test context can query it.
+pub fn wrapped_package_hash(name: &str) -> String {
+    format!("{}-wrapped-package-hash", name)
+}
+
+/// The access URef of the contract package, needed to add versions and manage groups.
+pub fn access_uref(name: &str) -> String {
+    format!("{}-access-uref", name)
+}
+
+/// The contract hash of the latest version, wrapped in a URef. Used by all but `locked-with-share`.
+pub fn wrapped(name: &str) -> String {
+    format!("{}-wrapped", name)
+}
+
+/// The contract hash of the latest version of `locked-with-share`, wrapped in a URef.
+pub fn wrapped_hash(name: &str) -> String {
+    format!("{}-wrapped-hash", name)
+}
+
+/// The `owner` group URef of the sharing contract.
+pub fn owner_access(name: &str) -> String {
+    format!("{}-owner-access", name)
+}
+
+/// The `operator` group URef of the receiver contract, allowed to make it call other contracts.
+pub fn operator_access(name: &str) -> String {
+    format!("{}-operator-access", name)
+}
+
+/// Address of the purse the sharing contract holds deposit fees in, wrapped in a URef.
+pub fn wrapped_fee_purse_address(name: &str) -> String {
+    format!("{}-wrapped-fee-purse-address", name)
+}
+
+/// Name a claimed URef is stored under: its label, prefixed with the package it grants access to if known,
+/// so URefs of several contracts do not collide.
+pub fn claimed_uref(source_package: Option<ContractPackageHash>, label: &str) -> String {
+    match source_package {
+        Some(source) => format!("{}-{}", source.to_formatted_string(), label),
+        None => String::from(label),
+    }
+}
@@ -0,0 +1,92 @@
+//! Names, argument builders and return value decoders of the URef sharing contracts.
+//!
+//! The contracts read their arguments and named keys through the constants of this crate,
+//! so code building deploys with it can not drift apart from them.
+#![no_std]
+
+extern crate alloc;
+
+pub mod arg_errors;
+pub mod args;
+pub mod decode;
+pub mod entry_points;
+pub mod keys;
+pub mod locked;
+pub mod locked_with_share;
+pub mod schema;
+pub mod share;
+
+use alloc::{string::String, vec::Vec};
+
+use casper_types::{
+    account::AccountHash, bytesrepr::ToBytes, CLTyped, ContractPackageHash, Key, PublicKey,
+    RuntimeArgs, URef,
+};
+
+/// A stored URef, as `(source package, label, uref)`.
+pub type Entry = (Option<ContractPackageHash>, String, URef);
+
+/// Label of entries stored without one, also the name of the group the example contracts restrict access to.
+pub const DEFAULT_LABEL: &str = "admin";
+
+/// Interface the sharing contract reports from `version`, bumped whenever its entry points change
+/// incompatibly. The testing contract refuses to call a sharing contract reporting another one.
+pub const SHARE_INTERFACE_VERSION: u32 = 1;
+
+/// The account, or contract package, an entry point acts on.
+/// Each variant is passed under the argument the contracts read it from.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Recipient {
+    /// Passed as `account_pubkey`.
+    PublicKey(PublicKey),
+    /// Passed as `account_hash`.
+    AccountHash(AccountHash),
+    /// Passed as `recipient`, a `Key::Account` or the `Key::Hash` of a contract package.
+    Key(Key),
+}
+
+impl Recipient {
+    fn insert_into(self, runtime_args: &mut RuntimeArgs) {
+        match self {
+            Recipient::PublicKey(public_key) => {
+                insert(runtime_args, args::ACCOUNT_PUBKEY, public_key)
+            }
+            Recipient::AccountHash(account_hash) => {
+                insert(runtime_args, args::ACCOUNT_HASH, account_hash)
+            }
+            Recipient::Key(key) => insert(runtime_args, args::RECIPIENT, key),
+        }
+    }
+}
+
+/// List version of `Recipient`, for entry points depositing for several accounts at once.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Recipients {
+    /// Passed as `account_pubkeys`.
+    PublicKeys(Vec<PublicKey>),
+    /// Passed as `account_hashes`.
+    AccountHashes(Vec<AccountHash>),
+    /// Passed as `recipients`, each a `Key::Account`.
+    Keys(Vec<Key>),
+}
+
+impl Recipients {
+    fn insert_into(self, runtime_args: &mut RuntimeArgs) {
+        match self {
+            Recipients::PublicKeys(public_keys) => {
+                insert(runtime_args, args::ACCOUNT_PUBKEYS, public_keys)
+            }
+            Recipients::AccountHashes(account_hashes) => {
+                insert(runtime_args, args::ACCOUNT_HASHES, account_hashes)
+            }
+            Recipients::Keys(keys) => insert(runtime_args, args::RECIPIENTS, keys),
+        }
+    }
+}
+
+/// Adds `value` under `name`. Every argument type the contracts read serializes, so this can not fail.
+fn insert<T: CLTyped + ToBytes>(runtime_args: &mut RuntimeArgs, name: &str, value: T) {
+    runtime_args
+        .insert(name, value)
+        .expect("argument should serialize");
+}
@@ -0,0 +1,170 @@
+//! Arguments of the testing contract storing its URefs in the sharing contract, `locked.wasm`.
+use alloc::{string::String, vec::Vec};
+
+use casper_types::{
+    account::AccountHash, contracts::ContractVersion, ContractPackageHash, PublicKey, RuntimeArgs,
+    URef, U512,
+};
+
+use crate::{args, insert};
+
+/// Install arguments.
+pub struct InstallArgs {
+    runtime_args: RuntimeArgs,
+}
+
+impl InstallArgs {
+    /// Deposits an `admin` URef for each of the `users` in the sharing contract at `share_hash`.
+    pub fn new(users: Vec<PublicKey>, share_hash: ContractPackageHash) -> Self {
+        let mut runtime_args = RuntimeArgs::new();
+        insert(&mut runtime_args, args::USERS, users);
+        insert(&mut runtime_args, args::SHARE_HASH, share_hash);
+        Self { runtime_args }
+    }
+
+    /// Accounts to deposit for that are only known by their account hash.
+    pub fn user_hashes(mut self, user_hashes: Vec<AccountHash>) -> Self {
+        insert(&mut self.runtime_args, args::USER_HASHES, user_hashes);
+        self
+    }
+
+    /// Contract packages to push an `admin` URef to.
+    pub fn contracts(mut self, contracts: Vec<ContractPackageHash>) -> Self {
+        insert(&mut self.runtime_args, args::CONTRACTS, contracts);
+        self
+    }
+
+    /// Accounts whose access lapses after `lease_duration` milliseconds, renewable or not.
+    pub fn contractors(
+        mut self,
+        contractors: Vec<PublicKey>,
+        lease_duration: u64,
+        renewable: bool,
+    ) -> Self {
+        insert(&mut self.runtime_args, args::CONTRACTORS, contractors);
+        insert(&mut self.runtime_args, args::LEASE_DURATION, lease_duration);
+        insert(&mut self.runtime_args, args::RENEWABLE, renewable);
+        self
+    }
+
+    /// Protected entry point calls allowed per role, `0` for unlimited.
+    pub fn allowances(mut self, allowances: Vec<(String, u32)>) -> Self {
+        insert(&mut self.runtime_args, args::ALLOWANCES, allowances);
+        self
+    }
+
+    /// Version of the sharing contract to call, instead of its latest one.
+    pub fn share_version(mut self, share_version: ContractVersion) -> Self {
+        insert(&mut self.runtime_args, args::SHARE_VERSION, share_version);
+        self
+    }
+
+    /// Motes moved from the installers main purse to pay the deposit fees of the sharing contract,
+    /// needed while it charges one. What is left goes back.
+    pub fn fee_budget(mut self, fee_budget: U512) -> Self {
+        insert(&mut self.runtime_args, args::FEE_BUDGET, fee_budget);
+        self
+    }
+
+    /// Deposits the URefs for accounts as part of `bundle`.
+    pub fn bundle(mut self, bundle: &str) -> Self {
+        insert(&mut self.runtime_args, args::BUNDLE, String::from(bundle));
+        self
+    }
+
+    pub fn build(self) -> RuntimeArgs {
+        self.runtime_args
+    }
+}
+
+/// Arguments of `get_access`, claiming every URef of `bundle` if one is given.
+/// The testing contract at `this_contract` tells which sharing contract to claim from,
+/// a `share_contract` passed along has to be that one.
+pub fn get_access(
+    this_contract: ContractPackageHash,
+    share_contract: Option<ContractPackageHash>,
+    bundle: Option<&str>,
+) -> RuntimeArgs {
+    let mut runtime_args = RuntimeArgs::new();
+    insert(&mut runtime_args, args::THIS_CONTRACT, this_contract);
+    if let Some(share_contract) = share_contract {
+        insert(&mut runtime_args, args::SHARE_CONTRACT, share_contract);
+    }
+    if let Some(bundle) = bundle {
+        insert(&mut runtime_args, args::BUNDLE, String::from(bundle));
+    }
+    runtime_args
+}
+
+/// Arguments of `group_access_only`, `check_access`, `metered_access`, `sweep_expired`, `renew`
+/// and `share_binding`.
+pub fn no_args() -> RuntimeArgs {
+    RuntimeArgs::new()
+}
+
+/// Arguments of `roles_of`.
+pub fn roles_of(account: AccountHash) -> RuntimeArgs {
+    let mut runtime_args = RuntimeArgs::new();
+    insert(&mut runtime_args, args::ACCOUNT, account);
+    runtime_args
+}
+
+/// Arguments of `get_roles`, `this_contract` being the package of the contract itself.
+/// Without an `account` it copies the roles of the caller, see `check_access`.
+pub fn get_roles(this_contract: ContractPackageHash, account: Option<AccountHash>) -> RuntimeArgs {
+    let mut runtime_args = RuntimeArgs::new();
+    insert(&mut runtime_args, args::THIS_CONTRACT, this_contract);
+    if let Some(account) = account {
+        insert(&mut runtime_args, args::ACCOUNT, account);
+    }
+    runtime_args
+}
+
+/// Arguments of `set_allowance`.
+pub fn set_allowance(role: &str, calls: u32) -> RuntimeArgs {
+    let mut runtime_args = RuntimeArgs::new();
+    insert(&mut runtime_args, args::ROLE, String::from(role));
+    insert(&mut runtime_args, args::CALLS, calls);
+    runtime_args
+}
+
+/// Arguments of `top_up`.
+pub fn top_up(account: AccountHash, role: &str, calls: u32) -> RuntimeArgs {
+    let mut runtime_args = reset_usage(account, role);
+    insert(&mut runtime_args, args::CALLS, calls);
+    runtime_args
+}
+
+/// Arguments of `reset_usage`.
+pub fn reset_usage(account: AccountHash, role: &str) -> RuntimeArgs {
+    let mut runtime_args = roles_of(account);
+    insert(&mut runtime_args, args::ROLE, String::from(role));
+    runtime_args
+}
+
+/// Arguments of `register_guardians`.
+pub fn register_guardians(guardians: Vec<AccountHash>, threshold: u8) -> RuntimeArgs {
+    let mut runtime_args = RuntimeArgs::new();
+    insert(&mut runtime_args, args::GUARDIANS, guardians);
+    insert(&mut runtime_args, args::THRESHOLD, threshold);
+    runtime_args
+}
+
+/// Arguments of `declare_lost`, a `share_contract` passed along has to be the one the contract deposits in.
+/// The deposit fee for the new account, if one is due, is paid from `purse`.
+pub fn declare_lost(
+    account: AccountHash,
+    new_account: AccountHash,
+    share_contract: Option<ContractPackageHash>,
+    purse: Option<URef>,
+) -> RuntimeArgs {
+    let mut runtime_args = roles_of(account);
+    insert(&mut runtime_args, args::NEW_ACCOUNT, new_account);
+    if let Some(share_contract) = share_contract {
+        insert(&mut runtime_args, args::SHARE_CONTRACT, share_contract);
+    }
+    if let Some(purse) = purse {
+        insert(&mut runtime_args, args::PURSE, purse);
+    }
+    runtime_args
+}
@@ -0,0 +1,104 @@
+//! Arguments of the testing contract storing its URefs itself, `locked_with_share.wasm`.
+use alloc::{string::String, vec::Vec};
+
+use casper_types::{account::AccountHash, ContractPackageHash, PublicKey, RuntimeArgs, URef};
+
+use crate::{args, insert, Recipient, Recipients};
+
+/// Install arguments.
+pub struct InstallArgs {
+    runtime_args: RuntimeArgs,
+}
+
+impl InstallArgs {
+    /// Stores an `admin` URef for each of the `users`.
+    pub fn new(users: Vec<PublicKey>) -> Self {
+        let mut runtime_args = RuntimeArgs::new();
+        insert(&mut runtime_args, args::USERS, users);
+        Self { runtime_args }
+    }
+
+    /// URefs are only released once `threshold` of the `approvers` approved them.
+    pub fn approvers(mut self, approvers: Vec<PublicKey>, threshold: u8) -> Self {
+        insert(&mut self.runtime_args, args::APPROVERS, approvers);
+        insert(&mut self.runtime_args, args::APPROVAL_THRESHOLD, threshold);
+        self
+    }
+
+    /// Holders of an `admin` URef count as approvers as well.
+    pub fn admin_approvers(mut self) -> Self {
+        insert(&mut self.runtime_args, args::ADMIN_APPROVERS, true);
+        self
+    }
+
+    pub fn build(self) -> RuntimeArgs {
+        self.runtime_args
+    }
+}
+
+/// Arguments of `get_access`, `this_contract` being the package of the contract itself.
+pub fn get_access(this_contract: ContractPackageHash) -> RuntimeArgs {
+    let mut runtime_args = RuntimeArgs::new();
+    insert(&mut runtime_args, args::THIS_CONTRACT, this_contract);
+    runtime_args
+}
+
+/// Arguments of `get_access_requests`, `this_contract` being the package of the contract itself.
+pub fn get_access_requests(this_contract: ContractPackageHash) -> RuntimeArgs {
+    get_access(this_contract)
+}
+
+/// Arguments of `group_access_only`, `retrieve_urefs`, `list_access_requests`, `pause`,
+/// `unpause` and `accept_ownership`.
+pub fn no_args() -> RuntimeArgs {
+    RuntimeArgs::new()
+}
+
+/// Arguments of `append_urefs`, one URef for each recipient.
+pub fn append_urefs(urefs: Vec<URef>, recipients: Recipients) -> RuntimeArgs {
+    let mut runtime_args = RuntimeArgs::new();
+    insert(&mut runtime_args, args::UREFS, urefs);
+    recipients.insert_into(&mut runtime_args);
+    runtime_args
+}
+
+/// Arguments of `approve_release`.
+pub fn approve_release(recipient: Recipient) -> RuntimeArgs {
+    let mut runtime_args = RuntimeArgs::new();
+    recipient.insert_into(&mut runtime_args);
+    runtime_args
+}
+
+/// Arguments of `approve_release`, approving with an `admin` URef.
+pub fn approve_release_with_uref(recipient: Recipient, uref: URef) -> RuntimeArgs {
+    let mut runtime_args = approve_release(recipient);
+    insert(&mut runtime_args, args::UREF, uref);
+    runtime_args
+}
+
+/// Arguments of `request_access`.
+pub fn request_access(role: &str, note: &str) -> RuntimeArgs {
+    let mut runtime_args = RuntimeArgs::new();
+    insert(&mut runtime_args, args::ROLE, String::from(role));
+    insert(&mut runtime_args, args::NOTE, String::from(note));
+    runtime_args
+}
+
+/// Arguments of `approve_access`.
+pub fn approve_access(account: AccountHash) -> RuntimeArgs {
+    let mut runtime_args = RuntimeArgs::new();
+    insert(&mut runtime_args, args::ACCOUNT, account);
+    runtime_args
+}
+
+/// Arguments of `deny_access`.
+pub fn deny_access(account: AccountHash) -> RuntimeArgs {
+    approve_access(account)
+}
+
+/// Arguments of `transfer_ownership`.
+pub fn transfer_ownership(new_owner: AccountHash) -> RuntimeArgs {
+    let mut runtime_args = RuntimeArgs::new();
+    insert(&mut runtime_args, args::NEW_OWNER, new_owner);
+    runtime_args
+}
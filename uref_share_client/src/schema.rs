@@ -0,0 +1,591 @@
+//! Entry points of the contracts, with the types of their arguments and return values.
+//!
+//! The contracts install exactly these, so off-chain tools can export them as a schema.
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use casper_types::{
+    account::AccountHash, CLType, CLTyped, ContractPackageHash, EntryPoint, EntryPointAccess,
+    EntryPointType, EntryPoints, Key, Parameter,
+};
+
+use crate::{
+    args,
+    decode::{AccessRequest, Push, ShareBinding, Usage},
+    entry_points, Entry,
+};
+
+/// Entry points of the URef sharing contract, `uref-share.wasm`.
+pub fn share() -> EntryPoints {
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::RETRIEVE_UREF,
+        vec![],
+        CLType::URef,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::CLAIM_UREFS,
+        vec![
+            Parameter::new(
+                args::SOURCE_PACKAGE.to_string(),
+                <Option<ContractPackageHash>>::cl_type(),
+            ),
+            Parameter::new(args::LABEL.to_string(), <Option<String>>::cl_type()),
+        ],
+        <Vec<Entry>>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::CLAIM_BUNDLE,
+        vec![Parameter::new(args::BUNDLE.to_string(), CLType::String)],
+        <Vec<Entry>>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::STORE_UREF,
+        vec![
+            Parameter::new(args::UREF.to_string(), CLType::URef),
+            Parameter::new(args::ACCOUNT_PUBKEY.to_string(), CLType::PublicKey),
+            Parameter::new(args::ACCOUNT_HASH.to_string(), AccountHash::cl_type()),
+            Parameter::new(args::RECIPIENT.to_string(), CLType::Key),
+            Parameter::new(args::LABEL.to_string(), CLType::String),
+            Parameter::new(
+                args::SOURCE_PACKAGE.to_string(),
+                ContractPackageHash::cl_type(),
+            ),
+            Parameter::new(args::PURSE.to_string(), CLType::URef),
+            Parameter::new(args::BUNDLE.to_string(), CLType::String),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::STORE_UREF_FOR_CONTRACT,
+        vec![
+            Parameter::new(args::UREF.to_string(), CLType::URef),
+            Parameter::new(
+                args::CONTRACT_PACKAGE.to_string(),
+                ContractPackageHash::cl_type(),
+            ),
+            Parameter::new(args::LABEL.to_string(), CLType::String),
+            Parameter::new(
+                args::SOURCE_PACKAGE.to_string(),
+                ContractPackageHash::cl_type(),
+            ),
+            Parameter::new(args::PURSE.to_string(), CLType::URef),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::DELEGATE,
+        vec![
+            Parameter::new(args::UREF.to_string(), CLType::URef),
+            Parameter::new(args::ACCOUNT_PUBKEY.to_string(), CLType::PublicKey),
+            Parameter::new(args::ACCOUNT_HASH.to_string(), AccountHash::cl_type()),
+            Parameter::new(args::RECIPIENT.to_string(), CLType::Key),
+            Parameter::new(args::PURSE.to_string(), CLType::URef),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::APPROVE_RELEASE,
+        vec![
+            Parameter::new(args::ACCOUNT_PUBKEY.to_string(), CLType::PublicKey),
+            Parameter::new(args::ACCOUNT_HASH.to_string(), AccountHash::cl_type()),
+            Parameter::new(args::RECIPIENT.to_string(), CLType::Key),
+            Parameter::new(args::UREF.to_string(), CLType::URef),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::REVOKE_DELEGATION,
+        recipient_parameters(),
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::PAUSE,
+        vec![],
+        CLType::Unit,
+        EntryPointAccess::groups(&["owner"]),
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::UNPAUSE,
+        vec![],
+        CLType::Unit,
+        EntryPointAccess::groups(&["owner"]),
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::TRANSFER_OWNERSHIP,
+        vec![
+            Parameter::new(args::NEW_OWNER.to_string(), AccountHash::cl_type()),
+            Parameter::new(args::PACKAGE_ACCESS.to_string(), CLType::URef),
+        ],
+        CLType::Unit,
+        EntryPointAccess::groups(&["owner"]),
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::SET_QUOTAS,
+        vec![
+            Parameter::new(args::MAX_PENDING_PER_DEPOSITOR.to_string(), CLType::U32),
+            Parameter::new(args::MAX_ENTRIES_PER_RECIPIENT.to_string(), CLType::U32),
+            Parameter::new(args::MAX_TOTAL_ENTRIES.to_string(), CLType::U32),
+        ],
+        CLType::Unit,
+        EntryPointAccess::groups(&["owner"]),
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::SET_FEE,
+        vec![
+            Parameter::new(args::FEE.to_string(), CLType::U512),
+            Parameter::new(args::FEE_EXPIRY.to_string(), CLType::U64),
+        ],
+        CLType::Unit,
+        EntryPointAccess::groups(&["owner"]),
+        EntryPointType::Contract,
+    ));
+    let mut reclaim_parameters = recipient_parameters();
+    reclaim_parameters.push(Parameter::new(args::LABEL.to_string(), CLType::String));
+    reclaim_parameters.push(Parameter::new(
+        args::SOURCE_PACKAGE.to_string(),
+        ContractPackageHash::cl_type(),
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::RECLAIM_UREF,
+        reclaim_parameters,
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::EXPIRE_ENTRIES,
+        recipient_parameters(),
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::GET_USAGE,
+        recipient_parameters(),
+        Usage::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::ACCEPT_OWNERSHIP,
+        vec![Parameter::new(
+            args::PACKAGE_ACCESS.to_string(),
+            CLType::URef,
+        )],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::VERSION,
+        vec![],
+        CLType::U32,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::PUSHED_ENTRY,
+        vec![],
+        <Option<Push>>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points
+}
+
+/// Entry points of the testing contract storing its URefs in the sharing contract, `locked.wasm`.
+pub fn locked() -> EntryPoints {
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::GROUP_ACCESS_ONLY,
+        vec![],
+        CLType::Unit,
+        EntryPointAccess::groups(&["admin"]),
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::GET_ACCESS,
+        vec![
+            Parameter::new(
+                args::THIS_CONTRACT.to_string(),
+                ContractPackageHash::cl_type(),
+            ),
+            Parameter::new(
+                args::SHARE_CONTRACT.to_string(),
+                ContractPackageHash::cl_type(),
+            ),
+            Parameter::new(args::BUNDLE.to_string(), CLType::String),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Session,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::CHECK_ACCESS,
+        vec![],
+        <Vec<String>>::cl_type(),
+        EntryPointAccess::groups(&["admin"]),
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::ROLES_OF,
+        vec![Parameter::new(
+            args::ACCOUNT.to_string(),
+            AccountHash::cl_type(),
+        )],
+        <Vec<String>>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::GET_ROLES,
+        vec![
+            Parameter::new(
+                args::THIS_CONTRACT.to_string(),
+                ContractPackageHash::cl_type(),
+            ),
+            Parameter::new(args::ACCOUNT.to_string(), AccountHash::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Session,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::METERED_ACCESS,
+        vec![],
+        CLType::Unit,
+        EntryPointAccess::groups(&["admin"]),
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::SET_ALLOWANCE,
+        vec![
+            Parameter::new(args::ROLE.to_string(), CLType::String),
+            Parameter::new(args::CALLS.to_string(), CLType::U32),
+        ],
+        CLType::Unit,
+        EntryPointAccess::groups(&["admin"]),
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::TOP_UP,
+        vec![
+            Parameter::new(args::ACCOUNT.to_string(), AccountHash::cl_type()),
+            Parameter::new(args::ROLE.to_string(), CLType::String),
+            Parameter::new(args::CALLS.to_string(), CLType::U32),
+        ],
+        CLType::Unit,
+        EntryPointAccess::groups(&["admin"]),
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::RESET_USAGE,
+        vec![
+            Parameter::new(args::ACCOUNT.to_string(), AccountHash::cl_type()),
+            Parameter::new(args::ROLE.to_string(), CLType::String),
+        ],
+        CLType::Unit,
+        EntryPointAccess::groups(&["admin"]),
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::REGISTER_GUARDIANS,
+        vec![
+            Parameter::new(args::GUARDIANS.to_string(), <Vec<AccountHash>>::cl_type()),
+            Parameter::new(args::THRESHOLD.to_string(), CLType::U8),
+        ],
+        CLType::Unit,
+        EntryPointAccess::groups(&["admin"]),
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::DECLARE_LOST,
+        vec![
+            Parameter::new(args::ACCOUNT.to_string(), AccountHash::cl_type()),
+            Parameter::new(args::NEW_ACCOUNT.to_string(), AccountHash::cl_type()),
+            Parameter::new(
+                args::SHARE_CONTRACT.to_string(),
+                ContractPackageHash::cl_type(),
+            ),
+            Parameter::new(args::PURSE.to_string(), CLType::URef),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::SWEEP_EXPIRED,
+        vec![],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::RENEW,
+        vec![],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::SHARE_BINDING,
+        vec![],
+        ShareBinding::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::CONFIRM_DEPOSIT,
+        vec![Parameter::new(args::UREF.to_string(), CLType::URef)],
+        CLType::Unit,
+        EntryPointAccess::groups(&["admin"]),
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::ISSUE_DELEGATE_UREF,
+        vec![
+            Parameter::new(args::UREF.to_string(), CLType::URef),
+            Parameter::new(args::ACCOUNT.to_string(), AccountHash::cl_type()),
+        ],
+        CLType::URef,
+        EntryPointAccess::groups(&["admin"]),
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::REVOKE_DELEGATE_UREF,
+        vec![Parameter::new(args::UREF.to_string(), CLType::URef)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points
+}
+
+/// Entry points of the testing contract storing its URefs itself, `locked_with_share.wasm`.
+pub fn locked_with_share() -> EntryPoints {
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::GROUP_ACCESS_ONLY,
+        vec![],
+        CLType::Unit,
+        EntryPointAccess::groups(&["admin"]),
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::GET_ACCESS,
+        vec![Parameter::new(
+            args::THIS_CONTRACT.to_string(),
+            ContractPackageHash::cl_type(),
+        )],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Session,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::RETRIEVE_UREFS,
+        vec![],
+        CLType::List(Box::new(CLType::URef)),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::APPEND_UREFS,
+        vec![
+            Parameter::new(
+                args::UREFS.to_string(),
+                CLType::List(Box::new(CLType::URef)),
+            ),
+            Parameter::new(
+                args::ACCOUNT_PUBKEYS.to_string(),
+                CLType::List(Box::new(CLType::PublicKey)),
+            ),
+            Parameter::new(
+                args::ACCOUNT_HASHES.to_string(),
+                <Vec<AccountHash>>::cl_type(),
+            ),
+            Parameter::new(args::RECIPIENTS.to_string(), <Vec<Key>>::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::APPROVE_RELEASE,
+        vec![
+            Parameter::new(args::ACCOUNT_PUBKEY.to_string(), CLType::PublicKey),
+            Parameter::new(args::ACCOUNT_HASH.to_string(), AccountHash::cl_type()),
+            Parameter::new(args::RECIPIENT.to_string(), CLType::Key),
+            Parameter::new(args::UREF.to_string(), CLType::URef),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::CONFIRM_APPROVER,
+        vec![Parameter::new(args::UREF.to_string(), CLType::URef)],
+        CLType::Unit,
+        EntryPointAccess::groups(&["admin"]),
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::REQUEST_ACCESS,
+        vec![
+            Parameter::new(args::ROLE.to_string(), CLType::String),
+            Parameter::new(args::NOTE.to_string(), CLType::String),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::LIST_ACCESS_REQUESTS,
+        vec![],
+        <Vec<AccessRequest>>::cl_type(),
+        EntryPointAccess::groups(&["admin"]),
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::GET_ACCESS_REQUESTS,
+        vec![Parameter::new(
+            args::THIS_CONTRACT.to_string(),
+            ContractPackageHash::cl_type(),
+        )],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Session,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::APPROVE_ACCESS,
+        vec![Parameter::new(
+            args::ACCOUNT.to_string(),
+            AccountHash::cl_type(),
+        )],
+        CLType::Unit,
+        EntryPointAccess::groups(&["admin"]),
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::DENY_ACCESS,
+        vec![Parameter::new(
+            args::ACCOUNT.to_string(),
+            AccountHash::cl_type(),
+        )],
+        CLType::Unit,
+        EntryPointAccess::groups(&["admin"]),
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::PAUSE,
+        vec![],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::UNPAUSE,
+        vec![],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::TRANSFER_OWNERSHIP,
+        vec![Parameter::new(
+            args::NEW_OWNER.to_string(),
+            AccountHash::cl_type(),
+        )],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::ACCEPT_OWNERSHIP,
+        vec![],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points
+}
+
+/// Entry points of the contract receiving URefs pushed by the sharing contract, `receiver.wasm`.
+pub fn receiver() -> EntryPoints {
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::RECEIVE_UREF,
+        vec![
+            Parameter::new(args::UREF.to_string(), CLType::URef),
+            Parameter::new(args::LABEL.to_string(), CLType::String),
+            Parameter::new(
+                args::SOURCE_PACKAGE.to_string(),
+                <Option<ContractPackageHash>>::cl_type(),
+            ),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::CALL_LOCKED,
+        vec![Parameter::new(
+            args::LOCKED_PACKAGE.to_string(),
+            ContractPackageHash::cl_type(),
+        )],
+        CLType::Unit,
+        EntryPointAccess::groups(&["operator"]),
+        EntryPointType::Contract,
+    ));
+    entry_points
+}
+
+/// Parameters identifying a recipient, callers supply exactly one of them.
+fn recipient_parameters() -> Vec<Parameter> {
+    vec![
+        Parameter::new(args::ACCOUNT_PUBKEY.to_string(), CLType::PublicKey),
+        Parameter::new(args::ACCOUNT_HASH.to_string(), AccountHash::cl_type()),
+        Parameter::new(args::RECIPIENT.to_string(), CLType::Key),
+    ]
+}
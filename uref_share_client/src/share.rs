@@ -0,0 +1,293 @@
+//! Arguments of the standalone URef sharing contract, `uref-share.wasm`.
+use alloc::{string::String, vec::Vec};
+
+use casper_types::{account::AccountHash, ContractPackageHash, PublicKey, RuntimeArgs, URef, U512};
+
+use crate::{args, insert, Recipient};
+
+/// Install arguments, every one of them optional.
+#[derive(Default)]
+pub struct InstallArgs {
+    runtime_args: RuntimeArgs,
+}
+
+impl InstallArgs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How many times a URef may be delegated onwards, one hop by default.
+    pub fn max_delegation_depth(mut self, depth: u8) -> Self {
+        insert(&mut self.runtime_args, args::MAX_DELEGATION_DEPTH, depth);
+        self
+    }
+
+    /// Entries are only released once `threshold` of the `approvers` approved them.
+    pub fn approvers(mut self, approvers: Vec<PublicKey>, threshold: u8) -> Self {
+        insert(&mut self.runtime_args, args::APPROVERS, approvers);
+        insert(&mut self.runtime_args, args::APPROVAL_THRESHOLD, threshold);
+        self
+    }
+
+    /// Holders of a URef `approver_package` confirms count as approvers as well.
+    pub fn approver_package(mut self, approver_package: ContractPackageHash) -> Self {
+        insert(
+            &mut self.runtime_args,
+            args::APPROVER_PACKAGE,
+            approver_package,
+        );
+        self
+    }
+
+    /// Limits on the pending entries, `0` stands for no limit.
+    pub fn quotas(
+        mut self,
+        max_pending_per_depositor: u32,
+        max_entries_per_recipient: u32,
+        max_total_entries: u32,
+    ) -> Self {
+        let limits = [
+            max_pending_per_depositor,
+            max_entries_per_recipient,
+            max_total_entries,
+        ];
+        for (quota, limit) in args::QUOTAS.iter().zip(limits.iter()) {
+            insert(&mut self.runtime_args, quota, *limit);
+        }
+        self
+    }
+
+    /// Deposit fee and after how many milliseconds an entry that paid it expires, `0` for never.
+    pub fn fee(mut self, fee: U512, fee_expiry: u64) -> Self {
+        insert(&mut self.runtime_args, args::FEE, fee);
+        insert(&mut self.runtime_args, args::FEE_EXPIRY, fee_expiry);
+        self
+    }
+
+    pub fn build(self) -> RuntimeArgs {
+        self.runtime_args
+    }
+}
+
+/// Arguments of `store_uref`.
+pub struct StoreUref {
+    runtime_args: RuntimeArgs,
+}
+
+impl StoreUref {
+    pub fn new(recipient: Recipient, uref: URef) -> Self {
+        let mut runtime_args = RuntimeArgs::new();
+        recipient.insert_into(&mut runtime_args);
+        insert(&mut runtime_args, args::UREF, uref);
+        Self { runtime_args }
+    }
+
+    /// Label to store the URef under, `admin` by default.
+    pub fn label(mut self, label: &str) -> Self {
+        insert(&mut self.runtime_args, args::LABEL, String::from(label));
+        self
+    }
+
+    /// The package the URef grants access to.
+    pub fn source_package(mut self, source_package: ContractPackageHash) -> Self {
+        insert(&mut self.runtime_args, args::SOURCE_PACKAGE, source_package);
+        self
+    }
+
+    /// Purse the deposit fee is paid from, required while a fee is set.
+    pub fn purse(mut self, purse: URef) -> Self {
+        insert(&mut self.runtime_args, args::PURSE, purse);
+        self
+    }
+
+    /// Bundle the entry is part of, for account recipients.
+    pub fn bundle(mut self, bundle: &str) -> Self {
+        insert(&mut self.runtime_args, args::BUNDLE, String::from(bundle));
+        self
+    }
+
+    pub fn build(self) -> RuntimeArgs {
+        self.runtime_args
+    }
+}
+
+/// Arguments of `store_uref_for_contract`.
+pub struct StoreUrefForContract {
+    runtime_args: RuntimeArgs,
+}
+
+impl StoreUrefForContract {
+    pub fn new(contract_package: ContractPackageHash, uref: URef) -> Self {
+        let mut runtime_args = RuntimeArgs::new();
+        insert(&mut runtime_args, args::CONTRACT_PACKAGE, contract_package);
+        insert(&mut runtime_args, args::UREF, uref);
+        Self { runtime_args }
+    }
+
+    /// Label the receiving contract stores the URef under, `admin` by default.
+    pub fn label(mut self, label: &str) -> Self {
+        insert(&mut self.runtime_args, args::LABEL, String::from(label));
+        self
+    }
+
+    /// The package the URef grants access to.
+    pub fn source_package(mut self, source_package: ContractPackageHash) -> Self {
+        insert(&mut self.runtime_args, args::SOURCE_PACKAGE, source_package);
+        self
+    }
+
+    /// Purse the deposit fee is paid from, required while a fee is set.
+    pub fn purse(mut self, purse: URef) -> Self {
+        insert(&mut self.runtime_args, args::PURSE, purse);
+        self
+    }
+
+    pub fn build(self) -> RuntimeArgs {
+        self.runtime_args
+    }
+}
+
+/// Arguments of `reclaim_uref`.
+pub struct ReclaimUref {
+    runtime_args: RuntimeArgs,
+}
+
+impl ReclaimUref {
+    pub fn new(recipient: Recipient) -> Self {
+        let mut runtime_args = RuntimeArgs::new();
+        recipient.insert_into(&mut runtime_args);
+        Self { runtime_args }
+    }
+
+    /// Label the entry was stored under, `admin` by default.
+    pub fn label(mut self, label: &str) -> Self {
+        insert(&mut self.runtime_args, args::LABEL, String::from(label));
+        self
+    }
+
+    /// The package the entry was stored for.
+    pub fn source_package(mut self, source_package: ContractPackageHash) -> Self {
+        insert(&mut self.runtime_args, args::SOURCE_PACKAGE, source_package);
+        self
+    }
+
+    pub fn build(self) -> RuntimeArgs {
+        self.runtime_args
+    }
+}
+
+/// Arguments of `retrieve_uref`.
+pub fn retrieve_uref() -> RuntimeArgs {
+    RuntimeArgs::new()
+}
+
+/// Arguments of `claim_urefs`, `None` matches any source package or label.
+pub fn claim_urefs(
+    source_package: Option<ContractPackageHash>,
+    label: Option<&str>,
+) -> RuntimeArgs {
+    let mut runtime_args = RuntimeArgs::new();
+    insert(&mut runtime_args, args::SOURCE_PACKAGE, source_package);
+    insert(&mut runtime_args, args::LABEL, label.map(String::from));
+    runtime_args
+}
+
+/// Arguments of `claim_bundle`.
+pub fn claim_bundle(bundle: &str) -> RuntimeArgs {
+    let mut runtime_args = RuntimeArgs::new();
+    insert(&mut runtime_args, args::BUNDLE, String::from(bundle));
+    runtime_args
+}
+
+/// Arguments of `delegate`.
+pub fn delegate(uref: URef, recipient: Recipient) -> RuntimeArgs {
+    let mut runtime_args = RuntimeArgs::new();
+    insert(&mut runtime_args, args::UREF, uref);
+    recipient.insert_into(&mut runtime_args);
+    runtime_args
+}
+
+/// Arguments of `delegate`, paying the deposit fee from `purse`.
+pub fn delegate_with_purse(uref: URef, recipient: Recipient, purse: URef) -> RuntimeArgs {
+    let mut runtime_args = delegate(uref, recipient);
+    insert(&mut runtime_args, args::PURSE, purse);
+    runtime_args
+}
+
+/// Arguments of `approve_release`.
+pub fn approve_release(recipient: Recipient) -> RuntimeArgs {
+    recipient_only(recipient)
+}
+
+/// Arguments of `approve_release`, approving with a `uref` the `approver_package` confirms.
+pub fn approve_release_with_uref(recipient: Recipient, uref: URef) -> RuntimeArgs {
+    let mut runtime_args = recipient_only(recipient);
+    insert(&mut runtime_args, args::UREF, uref);
+    runtime_args
+}
+
+/// Arguments of `revoke_delegation`.
+pub fn revoke_delegation(recipient: Recipient) -> RuntimeArgs {
+    recipient_only(recipient)
+}
+
+/// Arguments of `get_usage`.
+pub fn get_usage(recipient: Recipient) -> RuntimeArgs {
+    recipient_only(recipient)
+}
+
+/// Arguments of `expire_entries`.
+pub fn expire_entries(recipient: Recipient) -> RuntimeArgs {
+    recipient_only(recipient)
+}
+
+/// Arguments of `pause`.
+pub fn pause() -> RuntimeArgs {
+    RuntimeArgs::new()
+}
+
+/// Arguments of `unpause`.
+pub fn unpause() -> RuntimeArgs {
+    RuntimeArgs::new()
+}
+
+/// Arguments of `transfer_ownership`.
+pub fn transfer_ownership(new_owner: AccountHash, package_access: URef) -> RuntimeArgs {
+    let mut runtime_args = RuntimeArgs::new();
+    insert(&mut runtime_args, args::NEW_OWNER, new_owner);
+    insert(&mut runtime_args, args::PACKAGE_ACCESS, package_access);
+    runtime_args
+}
+
+/// Arguments of `accept_ownership`.
+pub fn accept_ownership(package_access: URef) -> RuntimeArgs {
+    let mut runtime_args = RuntimeArgs::new();
+    insert(&mut runtime_args, args::PACKAGE_ACCESS, package_access);
+    runtime_args
+}
+
+/// Arguments of `set_quotas`, `0` stands for no limit.
+pub fn set_quotas(
+    max_pending_per_depositor: u32,
+    max_entries_per_recipient: u32,
+    max_total_entries: u32,
+) -> RuntimeArgs {
+    InstallArgs::new()
+        .quotas(
+            max_pending_per_depositor,
+            max_entries_per_recipient,
+            max_total_entries,
+        )
+        .build()
+}
+
+/// Arguments of `set_fee`.
+pub fn set_fee(fee: U512, fee_expiry: u64) -> RuntimeArgs {
+    InstallArgs::new().fee(fee, fee_expiry).build()
+}
+
+fn recipient_only(recipient: Recipient) -> RuntimeArgs {
+    let mut runtime_args = RuntimeArgs::new();
+    recipient.insert_into(&mut runtime_args);
+    runtime_args
+}